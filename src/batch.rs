@@ -0,0 +1,295 @@
+use std::io;
+use std::io::{BufRead, Write};
+
+use checkers;
+use checkers::{
+	ActionError,
+	ColorScheme,
+	Command,
+	CommandError,
+	DrawReason,
+	Game,
+	GameState,
+	InputError,
+	MoveError,
+	Notation,
+	TokenError,
+	WinReason};
+
+/// The exit status when every line of the script was
+/// applied and the game is still in progress
+pub const EXIT_IN_PROGRESS : i32 = 0;
+
+/// The exit status when the script could not be read, or a
+/// line of it is not a legal move or a supported command
+pub const EXIT_SCRIPT_ERROR : i32 = 2;
+
+/// The exit status when player 1 won the game
+pub const EXIT_PLAYER1_WINS : i32 = 3;
+
+/// The exit status when player 2 won the game
+pub const EXIT_PLAYER2_WINS : i32 = 4;
+
+/// The exit status when the game was drawn
+pub const EXIT_DRAW : i32 = 5;
+
+///
+/// Applies the moves and commands of `script` to `game`, one per line,
+/// and writes one line to `writer` for each of them, e.g. `move 3 Red c3
+/// d4`. Empty lines and lines starting with `#` are skipped. The first
+/// line that cannot be applied is reported, e.g. `error 4 illegal-move
+/// f5`, and ends the script. Finally, the state of the game is written,
+/// e.g. `result win Black blocked 23`. Returns the exit status that
+/// reflects the result.
+///
+pub fn run_script
+<TRead : BufRead, TWrite : Write>
+(game : &mut Game, script : TRead, writer : &mut TWrite, colors : &ColorScheme, notation : Notation)
+-> io::Result<i32> {
+	for (index, line) in script.lines().enumerate() {
+		let line = try!(line);
+		let line = line.trim();
+		if line.is_empty() || line.starts_with('#') {
+			continue;
+		}
+
+		match apply_line(game, line, index + 1, colors, notation) {
+			Ok(output) => try!(writeln!(writer, "{}", output)),
+			Err(error) => {
+				try!(writeln!(writer, "error {} {}", index + 1, error));
+				return Ok(EXIT_SCRIPT_ERROR);
+			}
+		}
+	}
+
+	try!(writeln!(writer, "{}", result_line(game, colors)));
+	Ok(exit_status(game.state()))
+}
+
+/// The exit status that reflects `state`
+pub fn exit_status(state : &GameState) -> i32 {
+	match *state {
+		GameState::InProgress => EXIT_IN_PROGRESS,
+		GameState::GameOver{winner_id : 1, ..} => EXIT_PLAYER1_WINS,
+		GameState::GameOver{..} => EXIT_PLAYER2_WINS,
+		GameState::Draw{..} => EXIT_DRAW
+	}
+}
+
+// applies a move or a command, and returns the line that
+// reports it, or the error that stops the script
+fn apply_line
+(game : &mut Game, line : &str, line_number : usize, colors : &ColorScheme, notation : Notation)
+-> Result<String, String> {
+	if *game.state() != GameState::InProgress {
+		return Err("game-over".to_string());
+	}
+
+	let player_id = game.current_player().id;
+	let player_name = colors.name(player_id).to_string();
+	let size = game.board().number_rows();
+
+	let command = match checkers::parse_command(line) {
+		Some(Ok(command)) => command,
+		Some(Err(error)) => return Err(command_error_code(error)),
+		None => {
+			let input = try!(checkers::resolve_move(line, game).map_err(input_error_code));
+			let the_move = try!(game.find_move(&input.positions).map_err(move_error_code));
+			try!(input.check_capture_marker(&the_move).map_err(input_error_code));
+			try!(game.apply(the_move.clone()).map_err(move_error_code));
+
+			return Ok(format!("move {} {} {}", line_number, player_name,
+				checkers::format_move(&the_move, notation, game.variant(), size)));
+		}
+	};
+
+	let name = match command {
+		Command::Resign | Command::Quit => {
+			try!(game.resign(player_id).map_err(action_error_code));
+			"resign"
+		},
+		Command::Draw => {
+			try!(game.offer_draw(player_id).map_err(action_error_code));
+			"draw"
+		},
+		Command::Accept => {
+			try!(game.accept_draw(player_id).map_err(action_error_code));
+			"accept"
+		},
+		Command::Decline => {
+			try!(game.decline_draw(player_id).map_err(action_error_code));
+			"decline"
+		},
+		Command::Undo => if game.undo() { "undo" } else { return Err("nothing-to-undo".to_string()) },
+		Command::Redo => if game.redo() { "redo" } else { return Err("nothing-to-redo".to_string()) },
+		Command::Fen => return Ok(format!("fen {} {}", line_number, checkers::write_fen(game))),
+		Command::Moves => {
+			let moves : Vec<_> = game.legal_moves().iter()
+				.map(|the_move| checkers::format_move(the_move, notation, game.variant(), size))
+				.collect();
+			return Ok(format!("moves {} {}", line_number, moves.join(",")));
+		},
+		_ => return Err(format!("unsupported-command {}", line))
+	};
+
+	Ok(format!("{} {} {}", name, line_number, player_name))
+}
+
+// the last line of the output, e.g. `result in-progress`,
+// `result win Red captured 31` or `result draw agreement 40`
+fn result_line(game : &Game, colors : &ColorScheme) -> String {
+	match *game.state() {
+		GameState::InProgress => "result in-progress".to_string(),
+		GameState::GameOver{winner_id, reason, move_number} => {
+			let reason = match reason {
+				WinReason::AllPiecesCaptured => "captured",
+				WinReason::AllPiecesBlocked => "blocked",
+				WinReason::Resignation => "resignation",
+				WinReason::Time => "time"
+			};
+			format!("result win {} {} {}", colors.name(winner_id), reason, move_number)
+		},
+		GameState::Draw{reason, move_number} => {
+			let reason = match reason {
+				DrawReason::Repetition => "repetition",
+				DrawReason::MoveLimit => "move-limit",
+				DrawReason::Agreement => "agreement"
+			};
+			format!("result draw {} {}", reason, move_number)
+		}
+	}
+}
+
+fn move_error_code(error : MoveError) -> String {
+	let (code, position) = match error {
+		MoveError::IncompletePath => return "incomplete-move".to_string(),
+		MoveError::ShouldHaveJumped => return "must-jump".to_string(),
+		MoveError::GameFinished => return "game-over".to_string(),
+		MoveError::OffBoard{position} => ("off-board", position),
+		MoveError::LightTile{position} => ("light-tile", position),
+		MoveError::EmptyStart{position} => ("empty-start", position),
+		MoveError::OpponentPiece{position} => ("opponent-piece", position),
+		MoveError::InvalidDirection{position} => ("invalid-direction", position),
+		MoveError::InvalidMove{position} => ("illegal-move", position)
+	};
+
+	format!("{} {}", code, checkers::format_position(position))
+}
+
+fn input_error_code(error : InputError) -> String {
+	match error {
+		InputError::TooFewTokens => "too-few-positions".to_string(),
+		InputError::MixedMarkers => "mixed-markers".to_string(),
+		InputError::NotACapture => "not-a-capture".to_string(),
+		InputError::UnmarkedCapture => "unmarked-capture".to_string(),
+		InputError::NoMatchingMove{position} =>
+			format!("no-matching-move {}", checkers::format_position(position)),
+		InputError::AmbiguousMove{candidates} => {
+			let candidates : Vec<_> = candidates.iter()
+				.map(|the_move| the_move.path().iter()
+					.map(|position| checkers::format_position(*position))
+					.collect::<Vec<_>>()
+					.join(" "))
+				.collect();
+			format!("ambiguous-move {}", candidates.join(","))
+		},
+		InputError::InvalidTokens{tokens} => {
+			let tokens : Vec<_> = tokens.into_iter()
+				.map(|error| match error {
+					TokenError::MissingFile{token}
+						| TokenError::MissingRank{token}
+						| TokenError::ZeroRank{token}
+						| TokenError::InvalidCharacter{token, ..}
+						| TokenError::SquareOutOfRange{token, ..}
+						| TokenError::PositionTooLarge{token} => token
+				})
+				.collect();
+			format!("invalid-position {}", tokens.join(","))
+		}
+	}
+}
+
+fn command_error_code(error : CommandError) -> String {
+	match error {
+		CommandError::Unknown{command, ..} => format!("unknown-command {}", command),
+		CommandError::MissingArgument{command} => format!("missing-argument {}", command),
+		CommandError::UnexpectedArgument{command} => format!("unexpected-argument {}", command)
+	}
+}
+
+fn action_error_code(error : ActionError) -> String {
+	match error {
+		ActionError::GameFinished => "game-over",
+		ActionError::UnknownPlayer => unreachable!(),
+		ActionError::DrawAlreadyOffered => "draw-already-offered",
+		ActionError::DrawOfferPending => "draw-offer-pending",
+		ActionError::NoDrawOffered => "no-draw-offered"
+	}.to_string()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use checkers::{ColorScheme, Game, Notation};
+
+	fn run(game : &mut Game, script : &str) -> (i32, String) {
+		let mut output = Vec::new();
+		let status = run_script(
+			game, script.as_bytes(), &mut output, &ColorScheme::red_black(), Notation::Algebraic)
+			.unwrap();
+		(status, String::from_utf8(output).unwrap())
+	}
+
+	#[test]
+	fn moves_and_commands_are_reported() {
+		let mut game = Game::new();
+		let script = "# opening\n\nc3 d4\nf6-e5\nundo\nredo\nd4xf6\n";
+		let (status, output) = run(&mut game, script);
+		assert_eq!(EXIT_IN_PROGRESS, status);
+		assert_eq!(
+			concat!(
+				"move 3 Red c3 d4\n",
+				"move 4 Black f6 e5\n",
+				"undo 5 Red\n",
+				"redo 6 Black\n",
+				"move 7 Red d4 f6\n",
+				"result in-progress\n"),
+			output);
+	}
+
+	#[test]
+	fn first_error_ends_the_script() {
+		let mut game = Game::new();
+		let (status, output) = run(&mut game, "c3 d4\nf6 f5\ng7 h6\n");
+		assert_eq!(EXIT_SCRIPT_ERROR, status);
+		assert_eq!("move 1 Red c3 d4\nerror 2 light-tile f5\n", output);
+		assert_eq!(2, game.current_player().id);
+
+		let (status, output) = run(&mut Game::new(), "flip\n");
+		assert_eq!(EXIT_SCRIPT_ERROR, status);
+		assert_eq!("error 1 unsupported-command flip\n", output);
+	}
+
+	#[test]
+	fn exit_status_reflects_the_result() {
+		let (status, output) = run(&mut Game::new(), "c3 d4\nresign\n");
+		assert_eq!(EXIT_PLAYER1_WINS, status);
+		assert_eq!("move 1 Red c3 d4\nresign 2 Black\nresult win Red resignation 1\n", output);
+
+		let (status, _) = run(&mut Game::new(), "resign\n");
+		assert_eq!(EXIT_PLAYER2_WINS, status);
+
+		let (status, output) = run(&mut Game::new(), "draw\nc3 d4\naccept\n");
+		assert_eq!(EXIT_DRAW, status);
+		assert!(output.ends_with("accept 3 Black\nresult draw agreement 1\n"));
+
+		let (status, output) = run(&mut Game::new(), "draw\nc3 d4\ndraw\n");
+		assert_eq!(EXIT_SCRIPT_ERROR, status);
+		assert!(output.ends_with("error 3 draw-offer-pending\n"));
+
+		let (status, output) = run(&mut Game::new(), "resign\nc3 d4\n");
+		assert_eq!(EXIT_SCRIPT_ERROR, status);
+		assert_eq!("resign 1 Red\nerror 2 game-over\n", output);
+	}
+}
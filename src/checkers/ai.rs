@@ -1,8 +1,11 @@
+extern crate std;
+
 use checkers::player::Player;
+use checkers::piece::PieceType;
 use checkers::board::{Board, BoardPosition};
 use std::collections::HashSet;
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Direction {
 	/// The piece is moving such that its rank is increasing
 	IncreasingRank,
@@ -11,8 +14,148 @@ pub enum Direction {
 	DecreasingRank
 }
 
-// A move from one tile to an adjacent diagonal one
+impl Direction {
+	// the change in row when taking a single step forward
+	fn row_step(&self) -> isize {
+		match *self {
+			Direction::IncreasingRank => 1,
+			Direction::DecreasingRank => -1
+		}
+	}
+}
+
+/// A single step from one tile to a neighboring tile.
+/// Each component is either -1, 0 or 1.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MoveVector {
+	pub rows : isize,
+	pub columns : isize
+}
+
+impl MoveVector {
+	pub fn new(rows : isize, columns : isize) -> MoveVector {
+		MoveVector{ rows : rows, columns : columns }
+	}
+
+	/// The vector pointing the opposite way of this one
+	pub fn opposite(&self) -> MoveVector {
+		MoveVector::new(-self.rows, -self.columns)
+	}
+
+	/// All four diagonal steps
+	pub fn diagonals() -> Vec<MoveVector> {
+		vec![
+			MoveVector::new(-1, -1),
+			MoveVector::new(-1, 1),
+			MoveVector::new(1, -1),
+			MoveVector::new(1, 1)]
+	}
+
+	/// The two diagonal steps moving forward in `direction`
+	pub fn forward_diagonals(direction : Direction) -> Vec<MoveVector> {
+		let forward = direction.row_step();
+		vec![MoveVector::new(forward, -1), MoveVector::new(forward, 1)]
+	}
+
+	/// All four orthogonal steps
+	pub fn orthogonals() -> Vec<MoveVector> {
+		vec![
+			MoveVector::new(-1, 0),
+			MoveVector::new(0, -1),
+			MoveVector::new(0, 1),
+			MoveVector::new(1, 0)]
+	}
+
+	/// The orthogonal step moving forward in `direction`,
+	/// and the two sideways steps
+	pub fn forward_and_sideways(direction : Direction) -> Vec<MoveVector> {
+		let forward = direction.row_step();
+		vec![
+			MoveVector::new(forward, 0),
+			MoveVector::new(0, -1),
+			MoveVector::new(0, 1)]
+	}
+}
+
+/// Describes how a type of piece moves and captures
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Movement {
+	/// The directions the piece can move in without capturing
+	pub steps : Vec<MoveVector>,
+
+	/// The directions the piece can capture in
+	pub capture_steps : Vec<MoveVector>,
+
+	/// Whether the piece can travel any number of vacant tiles in a
+	/// direction, both when moving and when approaching and leaving
+	/// a captured piece. Otherwise the piece only moves one tile, and
+	/// only jumps adjacent pieces.
+	pub flying : bool,
+
+	/// Whether the piece may capture enemy kings
+	pub captures_kings : bool
+}
+
+impl Movement {
+	/// A man in American checkers moving in `direction`
+	pub fn diagonal_man(direction : Direction) -> Movement {
+		Movement{
+			steps : MoveVector::forward_diagonals(direction),
+			capture_steps : MoveVector::forward_diagonals(direction),
+			flying : false,
+			captures_kings : true}
+	}
+
+	/// A king in American checkers
+	pub fn diagonal_king() -> Movement {
+		Movement{
+			steps : MoveVector::diagonals(),
+			capture_steps : MoveVector::diagonals(),
+			flying : false,
+			captures_kings : true}
+	}
+
+	/// A man in Turkish draughts moving in `direction`
+	pub fn orthogonal_man(direction : Direction) -> Movement {
+		Movement{
+			steps : MoveVector::forward_and_sideways(direction),
+			capture_steps : MoveVector::forward_and_sideways(direction),
+			flying : false,
+			captures_kings : true}
+	}
+
+	/// A king in Turkish draughts
+	pub fn orthogonal_king() -> Movement {
+		Movement{
+			steps : MoveVector::orthogonals(),
+			capture_steps : MoveVector::orthogonals(),
+			flying : true,
+			captures_kings : true}
+	}
+}
+
+/// Describes what happens to captured pieces during a multi-jump
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CaptureRules {
+	/// Whether jumped pieces are removed from the board as soon as
+	/// they are jumped. Otherwise they are removed once the move is
+	/// complete, and block the jumping piece until then.
+	pub remove_immediately : bool,
+
+	/// Whether a jump may be followed by a jump in the opposite direction
+	pub allow_reversal : bool
+}
+
+impl CaptureRules {
+	/// The capture rules of American checkers
+	pub fn american() -> CaptureRules {
+		CaptureRules{ remove_immediately : false, allow_reversal : true }
+	}
+}
+
+// A move from one tile to another without capturing
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
 pub struct SimpleMove {
 	from_row : usize,
 	from_col : usize,
@@ -51,7 +194,56 @@ impl SimpleMove {
 	}
 }
 
+/// A complete move of a single piece, whether it is a simple move
+/// or a sequence of jumps
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct Move {
+	path : Vec<BoardPosition>,
+	captured : Vec<BoardPosition>
+}
+
+impl Move {
+	/// Creates a move where the piece visits the tiles in `path`,
+	/// starting with its origin, and captures the pieces on the tiles in
+	/// `captured`. A simple move captures nothing.
+	///
+	/// # Panics
+	///
+	/// Panics if `path` contains fewer than two positions.
+	pub fn new(path : Vec<BoardPosition>, captured : Vec<BoardPosition>) -> Move {
+		if path.len() < 2 {
+			panic!("A move must visit at least two tiles: {:?}", path)
+		}
+		
+		Move{ path : path, captured : captured }
+	}
+	
+	/// The tiles visited by the moving piece, starting with its origin
+	pub fn path(&self) -> &[BoardPosition] {
+		&self.path
+	}
+	
+	/// The tiles of the pieces captured by the move
+	pub fn captured(&self) -> &[BoardPosition] {
+		&self.captured
+	}
+	
+	pub fn start(&self) -> BoardPosition {
+		self.path[0]
+	}
+	
+	pub fn end(&self) -> BoardPosition {
+		*self.path.last().unwrap()
+	}
+	
+	pub fn is_jump(&self) -> bool {
+		!self.captured.is_empty()
+	}
+}
+
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
 pub struct JumpMove {
 	from_row : usize,
 	from_col : usize,
@@ -71,404 +263,287 @@ impl JumpMove {
 	pub fn jumps(&self) -> &Vec<JumpMove> {
 		&self.jumps
 	}
-	
-	pub fn contains_jump_sequence(&self, jumps : &[BoardPosition]) -> bool {
-		if jumps.len() == 0 {
-			true
-		} else {
-			self.contains_jump_sequence_recursive(jumps)
-		}
-	}
-	
-	fn contains_jump_sequence_recursive(&self, jumps : &[BoardPosition]) -> bool {
-		if jumps[0].row == self.from_row && jumps[0].column == self.from_col {
-			jumps.len() == 1 || self.jumps.iter()
-					.any(|subtree| subtree.contains_jump_sequence(&jumps[1..]))
-		} else {
-			false
-		}
+
+	/// Returns every complete jump sequence in this tree. Each
+	/// sequence begins with the position of the root.
+	pub fn jump_sequences(&self) -> Vec<Vec<BoardPosition>> {
+		let mut sequences = Vec::new();
+		let mut path = Vec::new();
+		self.jump_sequences_recursive(&mut path, &mut sequences);
+		sequences
 	}
-}
 
-/// Given the position of a main piece on a board, and the
-/// direction this man piece is moving, determines the simple
-/// moves available to this piece.
-pub fn find_simple_moves_for_man
-(board : &Board,
-		direction : Direction,
-		row : usize,
-		col : usize)
--> Vec<SimpleMove> {
-	let row_offset = match direction {
-		Direction::DecreasingRank => TileOffset::Negative(1),
-		Direction::IncreasingRank => TileOffset::Positive(1),
-	};
+	fn jump_sequences_recursive
+	(&self,
+			path : &mut Vec<BoardPosition>,
+			sequences : &mut Vec<Vec<BoardPosition>>) {
+		path.push(BoardPosition::new(self.from_row, self.from_col));
 
-	let mut moves = Vec::new();
+		if self.jumps.is_empty() {
+			sequences.push(path.clone());
+		} else {
+			for jump in &self.jumps {
+				jump.jump_sequences_recursive(path, sequences);
+			}
+		}
 
-	{
-		let col_offset = TileOffset::Negative(1);
-		push_simple_move_if_valid(
-			board, row, col, &row_offset, &col_offset, &mut moves);
+		path.pop();
 	}
 
-	{
-		let col_offset = TileOffset::Positive(1);
-		push_simple_move_if_valid(
-			board, row, col, &row_offset, &col_offset, &mut moves);
+	/// Removes every complete jump sequence for which `keep` returns
+	/// false. Branches of the tree that no longer lead to any of the
+	/// kept sequences are removed as well.
+	pub fn retain_jump_sequences<F>(&mut self, keep : &F)
+	where F : Fn(&[BoardPosition]) -> bool {
+		let mut path = Vec::new();
+		if !self.retain_jump_sequences_recursive(&mut path, keep) {
+			self.jumps.clear();
+		}
 	}
 
-	moves
+	// returns true if any sequence passing through this node is kept
+	fn retain_jump_sequences_recursive<F>
+	(&mut self, path : &mut Vec<BoardPosition>, keep : &F)
+	-> bool
+	where F : Fn(&[BoardPosition]) -> bool {
+		path.push(BoardPosition::new(self.from_row, self.from_col));
+
+		let kept = if self.jumps.is_empty() {
+			keep(path)
+		} else {
+			let jumps = std::mem::replace(&mut self.jumps, Vec::new());
+			for mut jump in jumps {
+				if jump.retain_jump_sequences_recursive(path, keep) {
+					self.jumps.push(jump);
+				}
+			}
+			!self.jumps.is_empty()
+		};
+
+		path.pop();
+		kept
+	}
 }
 
-/// Given the position of a main piece on a board, and the
-/// direction this man piece is moving, determines the simple
-/// moves available to this piece.
-pub fn find_jump_moves_for_man
+/// Given the position of a piece on a board, and the way
+/// this piece moves, determines the simple moves available
+/// to this piece.
+pub fn find_simple_moves
 (board : &Board,
-		player : &Player,
-		direction : Direction,
+		movement : &Movement,
 		row : usize,
 		col : usize)
--> JumpMove {
-	let mut jump_root = JumpMove::new(row, col);
-
-	let (pwnd_row_offset, jump_row_offset) = get_row_offsets(direction);
-
-	find_jump_moves_for_man_rustcursive(
-		board, player, &pwnd_row_offset, &jump_row_offset, &mut jump_root);
-
-	jump_root
-}
-
-fn find_jump_moves_for_man_rustcursive
-(board : &Board,
-		player : &Player,
-		pwnd_row_offset : &TileOffset,
-		jump_row_offset : &TileOffset,
-		jumps : &mut JumpMove) {
-	let col_offset_left = (TileOffset::Negative(1), TileOffset::Negative(2));
-	let col_offset_right = (TileOffset::Positive(1), TileOffset::Positive(2));
-
-	try_jump_moves_for_man(
-		board, player, &pwnd_row_offset, &jump_row_offset, col_offset_left, jumps);
-	try_jump_moves_for_man(
-		board, player, &pwnd_row_offset, &jump_row_offset, col_offset_right, jumps);
-}
-
-fn try_jump_moves_for_man
-(board : &Board,
-		player : &Player,
-		pwnd_row_offset : &TileOffset,
-		jump_row_offset : &TileOffset,
-		col_offset : (TileOffset, TileOffset),
-		jumps : &mut JumpMove) {
-	let start_row = jumps.from_row;
-	let start_col = jumps.from_col;
-	let (pwnd_col_offset, jump_col_offset) = col_offset;
-
-	let tile_on_board = is_tile_offset_in_bounds(
-		board, start_row, start_col, &jump_row_offset, &jump_col_offset);
-
-	if !tile_on_board {
-		return;
-	}
-
-	let (offset_row, offset_col)
-		= offset_tile(start_row, start_col, &pwnd_row_offset, &pwnd_col_offset);
-	let pwnd_tile = board.get_tile(offset_row, offset_col);
-
-	let (offset_row, offset_col)
-		= offset_tile(start_row, start_col, &jump_row_offset, &jump_col_offset);
-	let jump_tile = board.get_tile(offset_row, offset_col);
+-> Vec<SimpleMove> {
+	let start = BoardPosition::new(row, col);
+	let mut moves = Vec::new();
 
-	if jump_tile.get_piece().is_some() {
-		return;
-	}
+	for vector in &movement.steps {
+		let mut distance = 1;
+		while let Some(end) = offset_position(board, start, *vector, distance) {
+			if board.get_tile(end.row, end.column).get_piece().is_some() {
+				break;
+			}
 
-	let pwnd_piece_enemy = pwnd_tile
-		.get_piece()
-		.map(|piece| piece.get_player_id() != player.id)
-		.unwrap_or(false);
+			moves.push(SimpleMove::new(row, col, end.row, end.column));
 
-	if !pwnd_piece_enemy {
-		return;
+			if !movement.flying {
+				break;
+			}
+			distance += 1;
+		}
 	}
 
-	let mut the_move = JumpMove::new(offset_row, offset_col);
-
-	find_jump_moves_for_man_rustcursive(
-		board, player, &pwnd_row_offset, &jump_row_offset, &mut the_move);
-
-	jumps.jumps.push(the_move);
+	moves
 }
 
-pub fn find_jump_moves_for_king
+/// Given the position of a piece on a board, the player owning
+/// it, and the way this piece moves and captures, determines the
+/// jump moves available to this piece.
+pub fn find_jump_moves
 (board : &Board,
 		player : &Player,
+		movement : &Movement,
+		rules : &CaptureRules,
 		row : usize,
 		col : usize)
 -> JumpMove {
 	let mut jump_root = JumpMove::new(row, col);
 	let mut jumped_tiles = HashSet::new();
 
-	find_jump_moves_for_king_rustcursive(
-		board, player, BoardPosition::new(row, col), &mut jump_root, &mut jumped_tiles);
+	let search = JumpSearch{
+		board : board,
+		player : player,
+		movement : movement,
+		rules : rules,
+		init_position : BoardPosition::new(row, col)};
+
+	search.find_jumps_rustcursive(&mut jump_root, None, &mut jumped_tiles);
 
 	jump_root
 }
 
-fn find_jump_moves_for_king_rustcursive
-(board : &Board,
-		player : &Player,
-		init_position : BoardPosition,
-		curr_jump_root : &mut JumpMove,
-		jumped_tiles : &mut HashSet<BoardPosition>) {
-	push_jump_for_king_if_valid(
-		board,
-		player,
-		init_position,
-		curr_jump_root,
-		jumped_tiles,
-		TileOffset::Negative(1),
-		TileOffset::Negative(2),
-		TileOffset::Negative(1),
-		TileOffset::Negative(2));
-
-	push_jump_for_king_if_valid(
-		board,
-		player,
-		init_position,
-		curr_jump_root,
-		jumped_tiles,
-		TileOffset::Negative(1),
-		TileOffset::Negative(2),
-		TileOffset::Positive(1),
-		TileOffset::Positive(2));
-
-	push_jump_for_king_if_valid(
-		board,
-		player,
-		init_position,
-		curr_jump_root,
-		jumped_tiles,
-		TileOffset::Positive(1),
-		TileOffset::Positive(2),
-		TileOffset::Negative(1),
-		TileOffset::Negative(2));
-
-	push_jump_for_king_if_valid(
-		board,
-		player,
-		init_position,
-		curr_jump_root,
-		jumped_tiles,
-		TileOffset::Positive(1),
-		TileOffset::Positive(2),
-		TileOffset::Positive(1),
-		TileOffset::Positive(2));
+// The parameters that stay fixed while searching for jumps
+struct JumpSearch<'a> {
+	board : &'a Board,
+	player : &'a Player,
+	movement : &'a Movement,
+	rules : &'a CaptureRules,
+	init_position : BoardPosition
 }
 
-fn push_jump_for_king_if_valid
-(board : &Board,
-		player : &Player,
-		init_position : BoardPosition,
-		curr_jump_root : &mut JumpMove,
-		jumped_tiles : &mut HashSet<BoardPosition>,
-		pwnd_row_offset : TileOffset,
-		jump_row_offset : TileOffset,
-		pwnd_col_offset : TileOffset,
-		jump_col_offset : TileOffset) {
-	let start_row = curr_jump_root.from_row;
-	let start_col = curr_jump_root.from_col;
-
-	let tile_on_board = is_tile_offset_in_bounds(
-		board, start_row, start_col, &jump_row_offset, &jump_col_offset);
-	if !tile_on_board {
-		return;
-	}
-
-	let (jumped_row, jumped_col)
-		= offset_tile(start_row, start_col, &pwnd_row_offset, &pwnd_col_offset);
-	let pwnd_tile = board.get_tile(jumped_row, jumped_col);
-
-	let (end_row, end_col)
-		= offset_tile(start_row, start_col, &jump_row_offset, &jump_col_offset);
-	let end_tile = board.get_tile(end_row, end_col);
+impl<'a> JumpSearch<'a> {
+	// checks if the jumping piece can pass through or land on a tile
+	fn is_vacant
+	(&self, position : BoardPosition, jumped_tiles : &HashSet<BoardPosition>)
+	-> bool {
+		// The initial position of the jumping piece is OK to jump back to. This is because
+		// the jumping piece "floats" around the board while the other pieces remain fixed.
+		if position == self.init_position {
+			return true;
+		}
 
-	let tile_blocked = end_tile.get_piece().is_some();
+		if self.rules.remove_immediately && jumped_tiles.contains(&position) {
+			return true;
+		}
 
-	// The initial position of the jumping piece is OK to jump back to. This is because
-	// the jumping piece "floats" around the board while the other pieces remain fixed.
-	let at_initial_position = init_position == BoardPosition::new(end_row, end_col);
-	if tile_blocked && !at_initial_position {
-		return;
+		self.board.get_tile(position.row, position.column).get_piece().is_none()
 	}
 
-	let pwnd_piece_enemy = pwnd_tile
-		.get_piece()
-		.map(|piece| piece.get_player_id() != player.id)
-		.unwrap_or(false);
-
-	if !pwnd_piece_enemy {
-		return;
+	fn can_capture(&self, position : BoardPosition) -> bool {
+		self.board.get_tile(position.row, position.column)
+			.get_piece()
+			.map(|piece| piece.get_player_id() != self.player.id
+				&& (self.movement.captures_kings || piece.get_type() == PieceType::Man))
+			.unwrap_or(false)
 	}
 
-	// check to see if we have already jumped the tile
-	let jumped_position = BoardPosition::new(jumped_row, jumped_col);
-	if jumped_tiles.contains(&jumped_position) {
-		return;
+	fn find_jumps_rustcursive
+	(&self,
+			curr_jump_root : &mut JumpMove,
+			previous_step : Option<MoveVector>,
+			jumped_tiles : &mut HashSet<BoardPosition>) {
+		let start = BoardPosition::new(curr_jump_root.from_row, curr_jump_root.from_col);
+
+		for vector in &self.movement.capture_steps {
+			let vector = *vector;
+
+			let reversal = previous_step
+				.map(|step| step == vector.opposite())
+				.unwrap_or(false);
+			if reversal && !self.rules.allow_reversal {
+				continue;
+			}
+
+			// find the piece that would be jumped
+			let mut distance = 1;
+			let mut pwnd_position = offset_position(self.board, start, vector, distance);
+			if self.movement.flying {
+				while let Some(position) = pwnd_position {
+					if !self.is_vacant(position, jumped_tiles) {
+						break;
+					}
+					distance += 1;
+					pwnd_position = offset_position(self.board, start, vector, distance);
+				}
+			}
+
+			let pwnd_position = match pwnd_position {
+				Some(position) => position,
+				None => continue
+			};
+
+			// check to see if we have already jumped the tile
+			if jumped_tiles.contains(&pwnd_position) || !self.can_capture(pwnd_position) {
+				continue;
+			}
+
+			jumped_tiles.insert(pwnd_position);
+
+			distance += 1;
+			while let Some(end) = offset_position(self.board, start, vector, distance) {
+				if !self.is_vacant(end, jumped_tiles) {
+					break;
+				}
+
+				let mut jump = JumpMove::new(end.row, end.column);
+				self.find_jumps_rustcursive(&mut jump, Some(vector), jumped_tiles);
+				curr_jump_root.jumps.push(jump);
+
+				if !self.movement.flying {
+					break;
+				}
+				distance += 1;
+			}
+
+			jumped_tiles.remove(&pwnd_position);
+		}
 	}
-
-	let mut jump = JumpMove::new(end_row, end_col);
-
-	jumped_tiles.insert(jumped_position);
-
-	find_jump_moves_for_king_rustcursive(board, player, init_position, &mut jump, jumped_tiles);
-
-	jumped_tiles.remove(&jumped_position);
-
-	curr_jump_root.jumps.push(jump);
-}
-
-fn get_row_offsets(direction : Direction) -> (TileOffset, TileOffset) {
-	let (pwnd_row_offset, jump_row_offset) = match direction {
-		Direction::DecreasingRank =>
-			(TileOffset::Negative(1), TileOffset::Negative(2)),
-		Direction::IncreasingRank =>
-			(TileOffset::Positive(1), TileOffset::Positive(2))
-	};
-
-	(pwnd_row_offset, jump_row_offset) 
 }
 
-/// Given the position of a king piece on a board, determines
-/// the simple moves available to this piece.
-///
-/// This function does not require a Direction like the
-/// find_simple_moves_for_man function, because kings can move
-/// in all directions.
-pub fn find_simple_moves_for_king
-(board : &Board,
-		row : usize,
-		col : usize)
--> Vec<SimpleMove> {
-	let mut moves = Vec::new();
-
-	{
-		let row_offset = TileOffset::Negative(1);
-		let col_offset = TileOffset::Negative(1);
-		push_simple_move_if_valid(
-			board, row, col, &row_offset, &col_offset, &mut moves);
-	}
-
-	{
-		let row_offset = TileOffset::Negative(1);
-		let col_offset = TileOffset::Positive(1);
-		push_simple_move_if_valid(
-			board, row, col, &row_offset, &col_offset, &mut moves);
-	}
-
-	{
-		let row_offset = TileOffset::Positive(1);
-		let col_offset = TileOffset::Negative(1);
-		push_simple_move_if_valid(
-			board, row, col, &row_offset, &col_offset, &mut moves);
-	}
-
-	{
-		let row_offset = TileOffset::Positive(1);
-		let col_offset = TileOffset::Positive(1);
-		push_simple_move_if_valid(
-			board, row, col, &row_offset, &col_offset, &mut moves);
+/// Determines the positions of the pieces captured when `player`
+/// makes the jump sequence `jumps`. Each jump captures the first
+/// enemy piece between its start and end positions that has not
+/// already been captured by an earlier jump in the sequence.
+pub fn find_captured_positions
+(board : &Board, player : &Player, jumps : &[BoardPosition])
+-> Vec<BoardPosition> {
+	let mut captured = Vec::new();
+
+	if jumps.is_empty() {
+		return captured;
 	}
 
-	moves
-}
-
-// checks if it is possible to make a simple move with the given row
-// and tile offset, and if so, adds the move to the vector
-fn push_simple_move_if_valid
-(board : &Board,
-		start_row : usize,
-		start_col : usize,
-		row_offset : &TileOffset,
-		col_offset : &TileOffset,
-		moves : &mut Vec<SimpleMove>) {
-	let tile_on_board = is_tile_offset_in_bounds(
-		board, start_row, start_col, &row_offset, &col_offset);
-	if tile_on_board {
-		let (offset_row, offset_col)
-			= offset_tile(start_row, start_col, &row_offset, &col_offset);
-		let tile = board.get_tile(offset_row, offset_col);
-		if tile.get_piece().is_none() {
-			let the_move = SimpleMove::new(
-				start_row, start_col, offset_row, offset_col);
-			moves.push(the_move);
+	for (from, to) in jumps.iter().zip(jumps[1..].iter()) {
+		let vector = MoveVector::new(
+			step_between(from.row, to.row),
+			step_between(from.column, to.column));
+
+		let mut distance = 1;
+		while let Some(position) = offset_position(board, *from, vector, distance) {
+			if position == *to {
+				break;
+			}
+
+			let enemy = board.get_tile(position.row, position.column)
+				.get_piece()
+				.map(|piece| piece.get_player_id() != player.id)
+				.unwrap_or(false);
+			if enemy && !captured.contains(&position) {
+				captured.push(position);
+				break;
+			}
+
+			distance += 1;
 		}
 	}
-}
-
-// This enum describes an offset direction and magnitude.
-enum TileOffset {
-	Positive(usize),
-	Negative(usize)
-}
-
-// offsets a value based on the given offset direction and magnitude 
-fn offset_value
-(start_value : usize, value_offset : &TileOffset)
--> usize {
-	match *value_offset {
-	TileOffset::Negative(magnitude) => start_value - magnitude,
-	TileOffset::Positive(magnitude) => start_value + magnitude,
-	}
-}
 
-// Offsets a tile based on the given offset direction
-// and magnitude in the row and column dimensions.
-//
-// Returns a 2 element tuple, where the first element
-// is the offset row, and the second element is the
-// offset column.
-fn offset_tile
-(start_row : usize,
-		start_col : usize,
-		row_offset : &TileOffset,
-		col_offset : &TileOffset)
--> (usize, usize) {
-	(offset_value(start_row, row_offset),
-			offset_value(start_col, col_offset))
+	captured
 }
 
-// checks if a value is in the given range using the given offset
-//TODO maybe a range object can be used here as a param instead
-// of the start and max values
-fn is_offset_value_in_range
-(start_value : usize,
-		max_value : usize,
-		value_offset : &TileOffset)
--> bool {
-	match *value_offset {
-	TileOffset::Negative(magnitude) => start_value >= magnitude,
-	TileOffset::Positive(magnitude) => start_value + magnitude <= max_value
+// Offsets a position by `distance` steps of `vector`.
+// Returns None if the resulting position is off the board.
+fn offset_position
+(board : &Board,
+		start : BoardPosition,
+		vector : MoveVector,
+		distance : usize)
+-> Option<BoardPosition> {
+	let row = start.row as isize + vector.rows * distance as isize;
+	let col = start.column as isize + vector.columns * distance as isize;
+
+	let row_on_board = row >= 0 && row < board.number_rows() as isize;
+	let col_on_board = col >= 0 && col < board.number_columns() as isize;
+
+	if row_on_board && col_on_board {
+		Some(BoardPosition::new(row as usize, col as usize))
+	} else {
+		None
 	}
 }
 
-// checks if a tile on the board can be reached when
-// moving from one position on the board to another
-fn is_tile_offset_in_bounds
-(board : &Board,
-		start_row : usize,
-		start_col : usize,
-		row_offset : &TileOffset,
-		col_offset : &TileOffset)
--> bool {
-	let max_row_index = board.number_rows() - 1;
-	let max_col_index = board.number_columns() - 1;
-
-	is_offset_value_in_range(start_row, max_row_index, row_offset)
-	&& is_offset_value_in_range(start_col, max_col_index, col_offset)
+// the single step in a dimension that leads from one value towards another
+fn step_between(from : usize, to : usize) -> isize {
+	(to as isize - from as isize).signum()
 }
 
 #[cfg(test)]
@@ -479,123 +554,42 @@ mod jump_tree {
 	use checkers::BoardPosition;
 	
 	#[test]
-	fn empty_sequence() {
-		let jump_tree = JumpMove::new(0, 0);
-		let result = jump_tree.contains_jump_sequence(&Vec::new());
-		assert_eq!(true, result);
-	}
-	
-	#[test]
-	fn single_element_sequence_matching_start_position() {
-		let jump_tree = JumpMove::new(0, 0);
-		let jumps = vec![BoardPosition::new(0, 0)];
-		let result = jump_tree.contains_jump_sequence(&jumps);
-		assert_eq!(true, result);
-	}
-	
-	#[test]
-	fn single_element_sequence_different_start_position() {
-		let jump_tree = JumpMove::new(0, 0);
-		let jumps = vec![BoardPosition::new(1, 1)];
-		let result = jump_tree.contains_jump_sequence(&jumps);
-		assert_eq!(false, result);
-	}
-	
-	#[test]
-	fn single_jump_empty_tree() {
-		let jump_tree = JumpMove::new(0, 0);
-		let jumps = vec![BoardPosition::new(0, 0), BoardPosition::new(2, 2)];
-		let result = jump_tree.contains_jump_sequence(&jumps);
-		assert_eq!(false, result);
-	}
-	
-	#[test]
-	fn single_jump_tree_containing_jump() {
-		let jump_tree = JumpMove::with_jumps(0, 0, vec![JumpMove::new(2, 2)]);
-		let jumps = vec![BoardPosition::new(0, 0), BoardPosition::new(2, 2)];
-		let result = jump_tree.contains_jump_sequence(&jumps);
-		assert_eq!(true, result);
-	}
-	
-	#[test]
-	fn single_jump_tree_missing_jump() {
-		let jump_tree = JumpMove::with_jumps(2, 2, vec![JumpMove::new(2, 0)]);
-		let jumps = vec![BoardPosition::new(2, 2), BoardPosition::new(2, 4)];
-		let result = jump_tree.contains_jump_sequence(&jumps);
-		assert_eq!(false, result);
-	}
-	
-	#[test]
-	fn branching_jump_tree_containing_jump() {
-		let jump_tree = JumpMove::with_jumps(
-			5, 5, vec![
-				JumpMove::new(3, 3),
-				JumpMove::new(3, 7),
-				JumpMove::new(7, 3),
-				JumpMove::new(7, 7)]);
-				
-		let jumps = vec![BoardPosition::new(5, 5), BoardPosition::new(7, 3)];
-		let result = jump_tree.contains_jump_sequence(&jumps);
-		assert_eq!(true, result);
-	}
-	
-	#[test]
-	fn branching_jump_tree_missing_jump() {
-		let jump_tree = JumpMove::with_jumps(
-			5, 5, vec![
-				JumpMove::new(3, 3),
-				JumpMove::new(3, 7),
-				JumpMove::new(7, 3),
-				JumpMove::new(7, 7)]);
-				
-		let jumps = vec![BoardPosition::new(5, 5), BoardPosition::new(5, 5)];
-		let result = jump_tree.contains_jump_sequence(&jumps);
-		assert_eq!(false, result);
-	}
-	
-	#[test]
-	fn multi_jump_tree_containing_single_jump() {
+	fn jump_sequences_of_branching_tree() {
 		let jump_tree = JumpMove::with_jumps(
 			5, 5, vec![
 				JumpMove::with_jumps(
 					3, 3, vec![
-						JumpMove::new(1, 1)])]);
-				
-		let jumps = vec![BoardPosition::new(5, 5), BoardPosition::new(3, 3)];
-		let result = jump_tree.contains_jump_sequence(&jumps);
-		assert_eq!(true, result);
+						JumpMove::new(1, 1)]),
+				JumpMove::new(7, 7)]);
+		
+		let exp_result = vec![
+			vec![
+				BoardPosition::new(5, 5),
+				BoardPosition::new(3, 3),
+				BoardPosition::new(1, 1)],
+			vec![
+				BoardPosition::new(5, 5),
+				BoardPosition::new(7, 7)]];
+		assert_eq!(exp_result, jump_tree.jump_sequences());
 	}
 	
 	#[test]
-	fn multi_jump_tree_missing_single_jump() {
-		let jump_tree = JumpMove::with_jumps(
+	fn retain_longest_jump_sequences() {
+		let mut jump_tree = JumpMove::with_jumps(
 			5, 5, vec![
 				JumpMove::with_jumps(
 					3, 3, vec![
-						JumpMove::new(1, 1)])]);
-				
-		let jumps = vec![
-			BoardPosition::new(5, 5),
-			BoardPosition::new(3, 3),
-			BoardPosition::new(5, 5)];
-		let result = jump_tree.contains_jump_sequence(&jumps);
-		assert_eq!(false, result);
-	}
-	
-	#[test]
-	fn multi_jump_tree_containing_multi_jump() {
-		let jump_tree = JumpMove::with_jumps(
+						JumpMove::new(1, 1)]),
+				JumpMove::new(7, 7)]);
+		
+		jump_tree.retain_jump_sequences(&|sequence| sequence.len() == 3);
+		
+		let exp_result = JumpMove::with_jumps(
 			5, 5, vec![
 				JumpMove::with_jumps(
 					3, 3, vec![
 						JumpMove::new(1, 1)])]);
-		
-		let jumps = vec![
-			BoardPosition::new(5, 5),
-			BoardPosition::new(3, 3),
-			BoardPosition::new(1, 1)];
-		let result = jump_tree.contains_jump_sequence(&jumps);
-		assert_eq!(true, result);
+		assert_eq!(exp_result, jump_tree);
 	}
 }
 
@@ -612,8 +606,8 @@ use checkers::Player;
 #[test]
 fn single_tile_board_has_no_moves() {
 	let board = Board::new(1, 1);
-	let result = find_simple_moves_for_man(
-		&board, Direction::IncreasingRank, 0, 0);
+	let result = find_simple_moves(
+		&board, &Movement::diagonal_man(Direction::IncreasingRank), 0, 0);
 	assert_eq!(Vec::<SimpleMove>::new(), result);
 }
 
@@ -623,8 +617,8 @@ fn test_move
 		start_col : usize,
 		exp_result : Vec<SimpleMove>) {
 	let board = Board::new(8, 8);
-	let result = find_simple_moves_for_man(
-		&board, dir, start_row, start_col);
+	let result = find_simple_moves(
+		&board, &Movement::diagonal_man(dir), start_row, start_col);
 	assert_eq!(exp_result, result);
 }
 
@@ -667,8 +661,8 @@ fn test_move_blocked
 	let tile = OccupiedTile::new(Box::new(piece));
 	board.set_tile(piece_row, piece_col, Box::new(tile));
 
-	let result = find_simple_moves_for_man(
-		&board, dir, start_row, start_col);
+	let result = find_simple_moves(
+		&board, &Movement::diagonal_man(dir), start_row, start_col);
 	assert_eq!(exp_result, result);
 }
 
@@ -693,8 +687,8 @@ use checkers::Player;
 #[test]
 fn single_tile_board_has_no_moves() {
 	let board = Board::new(1, 1);
-	let result = find_simple_moves_for_king(
-		&board, 0, 0);
+	let result = find_simple_moves(
+		&board, &Movement::diagonal_king(), 0, 0);
 	assert_eq!(Vec::<SimpleMove>::new(), result);
 }
 
@@ -703,8 +697,8 @@ fn test_move
 		start_col : usize,
 		exp_result : Vec<SimpleMove>) {
 	let board = Board::new(8, 8);
-	let result = find_simple_moves_for_king(
-		&board, start_row, start_col);
+	let result = find_simple_moves(
+		&board, &Movement::diagonal_king(), start_row, start_col);
 	assert_eq!(exp_result, result);
 }
 
@@ -741,8 +735,8 @@ fn test_move_blocked
 	let tile = OccupiedTile::new(Box::new(piece));
 	board.set_tile(piece_row, piece_col, Box::new(tile));
 
-	let result = find_simple_moves_for_king(
-		&board, start_row, start_col);
+	let result = find_simple_moves(
+		&board, &Movement::diagonal_king(), start_row, start_col);
 	assert_eq!(exp_result, result);
 }
 
@@ -782,6 +776,7 @@ mod man_piece {
 
 use super::super::super::*;
 use checkers::Board;
+use checkers::KingPiece;
 use checkers::ManPiece;
 use checkers::OccupiedTile;
 use checkers::Player;
@@ -792,8 +787,9 @@ fn test_jumping_alone
 	let player = Player{ id : 0 };
 	let direction = Direction::IncreasingRank;
 
-	let result = find_jump_moves_for_man(
-		&board, &player, direction, start_row, start_col);
+	let movement = Movement::diagonal_man(direction);
+	let result = find_jump_moves(
+		&board, &player, &movement, &CaptureRules::american(), start_row, start_col);
 
 	let exp_result = JumpMove::new(start_row, start_col);
 
@@ -823,8 +819,9 @@ fn test_single_jump_single_enemy
 	let enemy_tile = OccupiedTile::new(Box::new(enemy_piece));
 	board.set_tile(enemy_row, enemy_col, Box::new(enemy_tile));
 
-	let result = find_jump_moves_for_man(
-		&board, &player, direction, start_row, start_col);
+	let movement = Movement::diagonal_man(direction);
+	let result = find_jump_moves(
+		&board, &player, &movement, &CaptureRules::american(), start_row, start_col);
 
 	assert_eq!(exp_result, result);
 }
@@ -859,8 +856,9 @@ fn test_single_jump_two_enemies
 	let right_enemy_tile = OccupiedTile::new(Box::new(right_enemy_piece));
 	board.set_tile(right_enemy_row, right_enemy_col, Box::new(right_enemy_tile));
 
-	let result = find_jump_moves_for_man(
-		&board, &player, direction, start_row, start_col);
+	let movement = Movement::diagonal_man(direction);
+	let result = find_jump_moves(
+		&board, &player, &movement, &CaptureRules::american(), start_row, start_col);
 
 	assert_eq!(exp_result, result);
 }
@@ -886,8 +884,9 @@ fn test_jumping_friendly_piece
 	let left_tile = OccupiedTile::new(Box::new(left_piece));
 	board.set_tile(friendly_row, friendly_col, Box::new(left_tile));
 
-	let result = find_jump_moves_for_man(
-		&board, &player, direction, start_row, start_col);
+	let movement = Movement::diagonal_man(direction);
+	let result = find_jump_moves(
+		&board, &player, &movement, &CaptureRules::american(), start_row, start_col);
 
 	let exp_result = JumpMove::new(start_row, start_col);
 
@@ -919,8 +918,9 @@ fn test_single_jump_blocked
 	let block_tile = OccupiedTile::new(Box::new(block_piece));
 	board.set_tile(blocked_row, blocked_col, Box::new(block_tile));
 
-	let result = find_jump_moves_for_man(
-		&board, &player, direction, start_row, start_col);
+	let movement = Movement::diagonal_man(direction);
+	let result = find_jump_moves(
+		&board, &player, &movement, &CaptureRules::american(), start_row, start_col);
 
 	let exp_result = JumpMove::new(start_row, start_col);
 
@@ -954,8 +954,9 @@ fn jumping_two_forward_adjacent_enemies_left_blocked() {
 	let right_tile = OccupiedTile::new(Box::new(right_piece));
 	board.set_tile(5, 4, Box::new(right_tile));
 
-	let result = find_jump_moves_for_man(
-		&board, &player, direction, start_row, start_col);
+	let movement = Movement::diagonal_man(direction);
+	let result = find_jump_moves(
+		&board, &player, &movement, &CaptureRules::american(), start_row, start_col);
 
 	let exp_result = JumpMove::with_jumps(
 		start_row, start_col, vec![JumpMove::new(6, 5)]);
@@ -985,8 +986,9 @@ fn jumping_two_forward_adjacent_enemies_right_blocked() {
 	let right_tile = OccupiedTile::new(Box::new(right_piece));
 	board.set_tile(5, 4, Box::new(right_tile));
 
-	let result = find_jump_moves_for_man(
-		&board, &player, direction, start_row, start_col);
+	let movement = Movement::diagonal_man(direction);
+	let result = find_jump_moves(
+		&board, &player, &movement, &CaptureRules::american(), start_row, start_col);
 
 	let exp_result = JumpMove::with_jumps(
 		start_row, start_col, vec![JumpMove::new(6, 1)]);
@@ -994,6 +996,35 @@ fn jumping_two_forward_adjacent_enemies_right_blocked() {
 	assert_eq!(exp_result, result);
 }
 
+#[test]
+fn jumping_enemy_king_when_sparing_kings() {
+	let mut board = Board::new(8, 8);
+	let player = Player{ id : 0 };
+	let direction = Direction::IncreasingRank;
+	let start_row = 4;
+	let start_col = 3;
+
+	let opponent = Player{ id : 1 };
+
+	let king_piece = KingPiece::new(&opponent);
+	let king_tile = OccupiedTile::new(Box::new(king_piece));
+	board.set_tile(5, 2, Box::new(king_tile));
+
+	let man_piece = ManPiece::new(&opponent);
+	let man_tile = OccupiedTile::new(Box::new(man_piece));
+	board.set_tile(5, 4, Box::new(man_tile));
+
+	let movement = Movement{
+		captures_kings : false, .. Movement::diagonal_man(direction)};
+	let result = find_jump_moves(
+		&board, &player, &movement, &CaptureRules::american(), start_row, start_col);
+
+	let exp_result = JumpMove::with_jumps(
+		start_row, start_col, vec![JumpMove::new(6, 5)]);
+
+	assert_eq!(exp_result, result);
+}
+
 #[test]
 fn the_one_true_test() {
 	let mut board = Board::new(8, 8);
@@ -1037,8 +1068,9 @@ fn the_one_true_test() {
 	let start_row = 6;
 	let start_col = 3;
 
-	let result = find_jump_moves_for_man(
-		&board, &player, direction, start_row, start_col);
+	let movement = Movement::diagonal_man(direction);
+	let result = find_jump_moves(
+		&board, &player, &movement, &CaptureRules::american(), start_row, start_col);
 
 	let exp_result = JumpMove::with_jumps(
 		start_row,
@@ -1082,8 +1114,9 @@ fn no_adjacent_enemy_pieces() {
 	let start_row = 6;
 	let start_col = 3;
 
-	let result = find_jump_moves_for_king(
-		&board, &player, start_row, start_col);
+	let movement = Movement::diagonal_king();
+	let result = find_jump_moves(
+		&board, &player, &movement, &CaptureRules::american(), start_row, start_col);
 	let exp_result = JumpMove::new(start_row, start_col);
 
 	assert_eq!(exp_result, result);
@@ -1100,8 +1133,9 @@ fn test_single_jump
 	let enemy_tile = OccupiedTile::new(Box::new(enemy_piece));
 	board.set_tile(enemy_row, enemy_col, Box::new(enemy_tile));
 
-	let result = find_jump_moves_for_king(
-		&board, &player, 4, 3);
+	let movement = Movement::diagonal_king();
+	let result = find_jump_moves(
+		&board, &player, &movement, &CaptureRules::american(), 4, 3);
 
 	assert_eq!(exp_result, result);
 }
@@ -1139,8 +1173,9 @@ fn jump_multiple_directions() {
 	let start_row = 4;
 	let start_col = 3;
 
-	let result = find_jump_moves_for_king(
-		&board, &player, start_row, start_col);
+	let movement = Movement::diagonal_king();
+	let result = find_jump_moves(
+		&board, &player, &movement, &CaptureRules::american(), start_row, start_col);
 	let exp_result = JumpMove::with_jumps(
 		start_row,
 		start_col,
@@ -1183,8 +1218,9 @@ fn jump_in_a_circle() {
 	let enemy_tile4 = OccupiedTile::new(Box::new(enemy_piece4));
 	board.set_tile(5, 2, Box::new(enemy_tile4));
 
-	let result = find_jump_moves_for_king(
-		&board, &player, start_row, start_col);
+	let movement = Movement::diagonal_king();
+	let result = find_jump_moves(
+		&board, &player, &movement, &CaptureRules::american(), start_row, start_col);
 	let exp_result = JumpMove::with_jumps(
 		start_row,
 		start_col,
@@ -1205,4 +1241,115 @@ fn jump_in_a_circle() {
 
 }
 
+mod orthogonal_movement {
+
+use super::super::*;
+use checkers::Board;
+use checkers::ManPiece;
+use checkers::OccupiedTile;
+use checkers::Player;
+
+fn place_man(board : &mut Board, player : &Player, row : usize, col : usize) {
+	let piece = ManPiece::new(player);
+	let tile = OccupiedTile::new(Box::new(piece));
+	board.set_tile(row, col, Box::new(tile));
+}
+
+#[test]
+fn man_moves_forward_and_sideways() {
+	let board = Board::new(8, 8);
+	let movement = Movement::orthogonal_man(Direction::DecreasingRank);
+
+	let result = find_simple_moves(&board, &movement, 4, 0);
+
+	let exp_result = vec![
+		SimpleMove::new(4, 0, 3, 0),
+		SimpleMove::new(4, 0, 4, 1)];
+	assert_eq!(exp_result, result);
+}
+
+#[test]
+fn flying_king_stops_at_blocking_piece() {
+	let mut board = Board::new(4, 4);
+	let player = Player{ id : 0 };
+	place_man(&mut board, &player, 0, 3);
+	place_man(&mut board, &player, 3, 0);
+
+	let result = find_simple_moves(&board, &Movement::orthogonal_king(), 0, 0);
+
+	let exp_result = vec![
+		SimpleMove::new(0, 0, 0, 1),
+		SimpleMove::new(0, 0, 0, 2),
+		SimpleMove::new(0, 0, 1, 0),
+		SimpleMove::new(0, 0, 2, 0)];
+	assert_eq!(exp_result, result);
+}
+
+#[test]
+fn flying_king_jump_without_reversal() {
+	let mut board = Board::new(8, 8);
+	let player = Player{ id : 0 };
+	let opponent = Player{ id : 1 };
+	place_man(&mut board, &opponent, 0, 1);
+	place_man(&mut board, &opponent, 0, 5);
+
+	let rules = CaptureRules{ remove_immediately : true, allow_reversal : false };
+	let result = find_jump_moves(
+		&board, &player, &Movement::orthogonal_king(), &rules, 0, 3);
+
+	let exp_result = JumpMove::with_jumps(
+		0, 3, vec![
+			JumpMove::new(0, 0),
+			JumpMove::new(0, 6),
+			JumpMove::new(0, 7)]);
+	assert_eq!(exp_result, result);
+}
+
+#[test]
+fn flying_king_jump_with_reversal() {
+	let mut board = Board::new(8, 8);
+	let player = Player{ id : 0 };
+	let opponent = Player{ id : 1 };
+	place_man(&mut board, &opponent, 0, 1);
+	place_man(&mut board, &opponent, 0, 5);
+
+	let rules = CaptureRules{ remove_immediately : true, allow_reversal : true };
+	let result = find_jump_moves(
+		&board, &player, &Movement::orthogonal_king(), &rules, 0, 3);
+
+	let exp_result = JumpMove::with_jumps(
+		0, 3, vec![
+			JumpMove::with_jumps(
+				0, 0, vec![
+					JumpMove::new(0, 6),
+					JumpMove::new(0, 7)]),
+			JumpMove::with_jumps(
+				0, 6, vec![
+					JumpMove::new(0, 0)]),
+			JumpMove::with_jumps(
+				0, 7, vec![
+					JumpMove::new(0, 0)])]);
+	assert_eq!(exp_result, result);
+}
+
+#[test]
+fn captured_positions_of_flying_jumps() {
+	let mut board = Board::new(8, 8);
+	let player = Player{ id : 0 };
+	let opponent = Player{ id : 1 };
+	place_man(&mut board, &opponent, 0, 4);
+	place_man(&mut board, &opponent, 3, 6);
+
+	let jumps = vec![
+		BoardPosition::new(0, 0),
+		BoardPosition::new(0, 6),
+		BoardPosition::new(5, 6)];
+	let result = find_captured_positions(&board, &player, &jumps);
+
+	let exp_result = vec![BoardPosition::new(0, 4), BoardPosition::new(3, 6)];
+	assert_eq!(exp_result, result);
+}
+
 }
+
+}
\ No newline at end of file
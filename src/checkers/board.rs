@@ -2,7 +2,15 @@ use checkers::piece::ManPiece;
 use checkers::player::Player;
 use checkers::tile::{EmptyTile, OccupiedTile, Tile};
 
+#[cfg(feature = "serialization")]
+use checkers::piece::{KingPiece, PieceType};
+#[cfg(feature = "serialization")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serialization")]
+use serde::de::Error;
+
 #[derive(PartialEq, Eq, Debug, Hash, Copy, Clone)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
 pub struct BoardPosition {
 	pub row : usize,
 	pub column : usize
@@ -14,17 +22,24 @@ impl BoardPosition {
 	}
 }
 
+/// Describes which corner tiles of a checkerboard are playable
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Orientation {
+	/// The lower left-hand corner (A1) is a playable tile
+	DarkCorner,
+
+	/// The lower left-hand corner (A1) is not a playable tile
+	LightCorner
+}
+
 pub struct Board {
     number_rows : usize,
     number_columns : usize,
     tiles : Vec<Box<Tile>>
 }
 
-const CHECKERBOARD_SIZE : usize = 8;
-const CHECKERS_NUMBER_TILES : usize = CHECKERBOARD_SIZE * CHECKERBOARD_SIZE;
-
 impl Board {
-	#[cfg(test)]
+	/// Creates a board with the given dimensions where every tile is empty
     pub fn new(number_rows : usize, number_columns : usize) -> Board {
 		let number_tiles = number_rows * number_columns;
         let mut board = Board {
@@ -40,30 +55,114 @@ impl Board {
         board
     }
 
-    pub fn new_checkerboard(player1 : &Player, player2 : &Player) -> Board {
+	/// Creates a `size` by `size` checkerboard where the pieces are
+	/// placed on the tiles selected by `orientation`. `player1` occupies
+	/// the lowest `starting_rows` ranks and `player2` occupies the highest
+	/// `starting_rows` ranks. For example, Canadian checkers is played on
+	/// a 12x12 board with five starting rows.
+	///
+	/// # Panics
+	///
+	/// Panics if `player1` and `player2` have the same ID, if `size` is
+	/// odd, or if the starting rows of the two players would overlap.
+	pub fn new_sized_checkerboard
+	(size : usize,
+			starting_rows : usize,
+			player1 : &Player,
+			player2 : &Player,
+			orientation : Orientation)
+	-> Board {
+		let mut board = Board::new_starting_board(size, starting_rows, player1, player2);
+
+		let parity_offset = match orientation {
+			Orientation::DarkCorner => 0,
+			Orientation::LightCorner => 1
+		};
+
+		for r in 0..size {
+			let player = Board::starting_player(size, starting_rows, r, player1, player2);
+			// whether the first tile of the row is playable
+			let even_row = (r + parity_offset) % 2 == 0;
+			match (player, even_row) {
+				(Some(player), true) => Board::fill_even_row(&mut board, player),
+				(Some(player), false) => Board::fill_odd_row(&mut board, player),
+				(None, _) => Board::fill_empty_row(&mut board)
+			}
+		}
+
+		board
+	}
+
+	/// Creates a `size` by `size` board where every tile is playable.
+	/// The first and last ranks are left empty, `player1` fills the
+	/// `starting_rows` ranks above the first rank and `player2` fills the
+	/// `starting_rows` ranks below the last rank.
+	///
+	/// # Panics
+	///
+	/// Panics if `player1` and `player2` have the same ID, if `size` is
+	/// odd, or if the starting rows of the two players would overlap.
+	pub fn new_sized_full_board
+	(size : usize,
+			starting_rows : usize,
+			player1 : &Player,
+			player2 : &Player)
+	-> Board {
+		let mut board = Board::new_starting_board(
+			size, starting_rows + 1, player1, player2);
+
+		for r in 0..size {
+			let back_rank = r == 0 || r + 1 == size;
+			let player = Board::starting_player(
+				size, starting_rows + 1, r, player1, player2);
+			match player {
+				Some(player) if !back_rank => Board::fill_full_row(&mut board, player),
+				_ => Board::fill_empty_row(&mut board)
+			}
+		}
+
+		board
+	}
+
+	// validates the starting layout and creates a board with no tiles
+	fn new_starting_board
+	(size : usize, starting_rows : usize, player1 : &Player, player2 : &Player)
+	-> Board {
 		if player1.id == player2.id {
 			panic!("Player 1 and Player 2 have the same ID: {}", player1.id)
 		}
-	
-        let mut board = Board {
-            number_rows : CHECKERBOARD_SIZE,
-            number_columns : CHECKERBOARD_SIZE,    	   
-            tiles : Vec::with_capacity(CHECKERS_NUMBER_TILES)
-        };
 
-        Board::fill_even_row(&mut board, player1);
-        Board::fill_odd_row(&mut board, player1);
-        Board::fill_even_row(&mut board, player1);
+		if size % 2 != 0 {
+			panic!("Board size must be even: {}", size)
+		}
 
-        Board::fill_empty_row(&mut board);
-        Board::fill_empty_row(&mut board);
+		if 2 * starting_rows > size {
+			panic!("{} starting rows do not fit on a board of size {}", starting_rows, size)
+		}
 
-        Board::fill_odd_row(&mut board, player2);
-        Board::fill_even_row(&mut board, player2);
-        Board::fill_odd_row(&mut board, player2);
+		Board {
+			number_rows : size,
+			number_columns : size,
+			tiles : Vec::with_capacity(size * size)
+		}
+	}
 
-        board
-    }
+	// determines the player who starts with pieces in a row, if any
+	fn starting_player<'a>
+	(size : usize,
+			starting_rows : usize,
+			row : usize,
+			player1 : &'a Player,
+			player2 : &'a Player)
+	-> Option<&'a Player> {
+		if row < starting_rows {
+			Some(player1)
+		} else if row >= size - starting_rows {
+			Some(player2)
+		} else {
+			None
+		}
+	}
 	
 	pub fn number_rows(&self) -> usize {
 		self.number_rows
@@ -130,9 +229,64 @@ impl Board {
         }
     }
 
+    fn fill_full_row(board : &mut Board, player : &Player) {
+        for _ in 0..board.number_columns {
+			let piece = ManPiece::new(player);
+            board.tiles.push(Box::new(OccupiedTile::new(Box::new(piece))));
+        }
+    }
+
     fn fill_empty_row(board : &mut Board) {
         for _ in 0..board.number_columns {
             board.tiles.push(Box::new(EmptyTile));
         }
     }
 }
+
+// the form a board is serialized in, with the owner and type of the
+// piece on each tile, row by row from the lowest rank
+#[cfg(feature = "serialization")]
+#[derive(Serialize, Deserialize)]
+struct SerializedBoard {
+	rows : usize,
+	columns : usize,
+	tiles : Vec<Option<(u32, PieceType)>>
+}
+
+#[cfg(feature = "serialization")]
+impl Serialize for Board {
+	fn serialize<S : Serializer>(&self, serializer : S) -> Result<S::Ok, S::Error> {
+		let tiles = self.tiles.iter()
+			.map(|tile| tile.get_piece()
+				.map(|piece| (piece.get_player_id(), piece.get_type())))
+			.collect();
+		let board = SerializedBoard{
+			rows : self.number_rows, columns : self.number_columns, tiles : tiles};
+		board.serialize(serializer)
+	}
+}
+
+#[cfg(feature = "serialization")]
+impl<'de> Deserialize<'de> for Board {
+	fn deserialize<D : Deserializer<'de>>(deserializer : D) -> Result<Board, D::Error> {
+		let serialized = try!(SerializedBoard::deserialize(deserializer));
+		if serialized.rows.checked_mul(serialized.columns) != Some(serialized.tiles.len()) {
+			return Err(D::Error::custom(format!(
+				"a {}x{} board cannot have {} tiles",
+				serialized.rows, serialized.columns, serialized.tiles.len())));
+		}
+
+		let mut board = Board::new(serialized.rows, serialized.columns);
+		for (index, tile) in serialized.tiles.into_iter().enumerate() {
+			if let Some((player_id, piece_type)) = tile {
+				let player = Player{id : player_id};
+				board.tiles[index] = match piece_type {
+					PieceType::Man => Box::new(OccupiedTile::new(Box::new(ManPiece::new(&player)))),
+					PieceType::King => Box::new(OccupiedTile::new(Box::new(KingPiece::new(&player))))
+				};
+			}
+		}
+
+		Ok(board)
+	}
+}
@@ -0,0 +1,211 @@
+extern crate std;
+
+use std::time::{Duration, Instant};
+
+/// How a player's clock is compensated for each of their moves
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum TimeBonus {
+	/// The clock runs for the whole of every move
+	None,
+
+	/// The duration is added to the clock after every move
+	Increment(Duration),
+
+	/// The clock does not run for the first part of every move,
+	/// up to the duration
+	Delay(Duration)
+}
+
+/// The time each player starts with, and how their time is compensated
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct TimeControl {
+	pub base : Duration,
+	pub bonus : TimeBonus
+}
+
+/// A chess clock for two players with the IDs 1 and 2. The clock does
+/// not read the time itself; the current time is passed to every method
+/// so that the clock can be driven by a user interface, a network
+/// connection or an engine match alike.
+pub struct Clock {
+	time_control : TimeControl,
+	remaining : [Duration; 2],
+
+	// the player whose clock is running, and when their turn started
+	turn : Option<(u32, Instant)>
+}
+
+impl Clock {
+	/// Creates a stopped clock where both players have the
+	/// base time of `time_control`
+	pub fn new(time_control : TimeControl) -> Clock {
+		Clock::with_times(time_control, time_control.base, time_control.base)
+	}
+
+	/// Creates a stopped clock where the players start with the given
+	/// times instead of the base time of `time_control`
+	pub fn with_times
+	(time_control : TimeControl, player1_time : Duration, player2_time : Duration)
+	-> Clock {
+		Clock{
+			time_control : time_control,
+			remaining : [player1_time, player2_time],
+			turn : None}
+	}
+
+	/// The ID of the player whose clock is running, if any
+	pub fn running_player(&self) -> Option<u32> {
+		self.turn.map(|(player_id, _)| player_id)
+	}
+
+	/// The time the player with ID `player_id` has left at `now`
+	///
+	/// # Panics
+	///
+	/// Panics if `player_id` is not 1 or 2.
+	pub fn remaining(&self, player_id : u32, now : Instant) -> Duration {
+		let remaining = self.remaining[Clock::player_index(player_id)];
+		match self.turn {
+			Some((running_id, started)) if running_id == player_id =>
+				remaining.checked_sub(self.time_used(started, now))
+					.unwrap_or(Duration::new(0, 0)),
+			_ => remaining
+		}
+	}
+
+	/// Whether the player with ID `player_id` has run out of time at `now`
+	///
+	/// # Panics
+	///
+	/// Panics if `player_id` is not 1 or 2.
+	pub fn is_flagged(&self, player_id : u32, now : Instant) -> bool {
+		self.remaining(player_id, now) == Duration::new(0, 0)
+	}
+
+	/// Starts the clock of the player with ID `player_id` at `now`.
+	/// The turn of any other player must be ended first.
+	///
+	/// # Panics
+	///
+	/// Panics if `player_id` is not 1 or 2, or if a clock is running.
+	pub fn start_turn(&mut self, player_id : u32, now : Instant) {
+		Clock::player_index(player_id);
+		if let Some((running_id, _)) = self.turn {
+			panic!("The clock of player {} is already running", running_id)
+		}
+
+		self.turn = Some((player_id, now));
+	}
+
+	/// Stops the running clock at `now`, and compensates the player for
+	/// their move. Returns false if the player ran out of time during
+	/// their turn, in which case they are not compensated.
+	pub fn end_turn(&mut self, now : Instant) -> bool {
+		let player_id = match self.turn {
+			Some((player_id, _)) => player_id,
+			None => return true
+		};
+
+		let remaining = self.remaining(player_id, now);
+		self.turn = None;
+
+		let player_index = Clock::player_index(player_id);
+		if remaining == Duration::new(0, 0) {
+			self.remaining[player_index] = remaining;
+			return false;
+		}
+
+		self.remaining[player_index] = match self.time_control.bonus {
+			TimeBonus::Increment(increment) => remaining + increment,
+			TimeBonus::None | TimeBonus::Delay(_) => remaining
+		};
+
+		true
+	}
+
+	// the time taken off the clock for a turn that started at `started`
+	fn time_used(&self, started : Instant, now : Instant) -> Duration {
+		let elapsed = now.duration_since(started);
+		match self.time_control.bonus {
+			TimeBonus::Delay(delay) =>
+				elapsed.checked_sub(delay).unwrap_or(Duration::new(0, 0)),
+			TimeBonus::None | TimeBonus::Increment(_) => elapsed
+		}
+	}
+
+	fn player_index(player_id : u32) -> usize {
+		match player_id {
+			1 => 0,
+			2 => 1,
+			_ => panic!("No clock for player {}", player_id)
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use std::time::{Duration, Instant};
+
+	fn seconds(seconds : u64) -> Duration {
+		Duration::from_secs(seconds)
+	}
+
+	#[test]
+	fn running_clock_counts_down() {
+		let mut clock = Clock::new(TimeControl{ base : seconds(60), bonus : TimeBonus::None });
+		let start = Instant::now();
+
+		clock.start_turn(1, start);
+		assert_eq!(Some(1), clock.running_player());
+		assert_eq!(seconds(50), clock.remaining(1, start + seconds(10)));
+		assert_eq!(seconds(60), clock.remaining(2, start + seconds(10)));
+
+		assert!(clock.end_turn(start + seconds(15)));
+		assert_eq!(None, clock.running_player());
+		assert_eq!(seconds(45), clock.remaining(1, start + seconds(30)));
+	}
+
+	#[test]
+	fn increment_is_added_after_move() {
+		let time_control = TimeControl{
+			base : seconds(60), bonus : TimeBonus::Increment(seconds(5))};
+		let mut clock = Clock::new(time_control);
+		let start = Instant::now();
+
+		clock.start_turn(2, start);
+		assert!(clock.end_turn(start + seconds(10)));
+		assert_eq!(seconds(55), clock.remaining(2, start + seconds(10)));
+	}
+
+	#[test]
+	fn delay_is_not_taken_off_clock() {
+		let time_control = TimeControl{
+			base : seconds(60), bonus : TimeBonus::Delay(seconds(5))};
+		let mut clock = Clock::new(time_control);
+		let start = Instant::now();
+
+		clock.start_turn(1, start);
+		assert_eq!(seconds(60), clock.remaining(1, start + seconds(3)));
+		assert!(clock.end_turn(start + seconds(8)));
+		assert_eq!(seconds(57), clock.remaining(1, start + seconds(8)));
+	}
+
+	#[test]
+	fn player_runs_out_of_time() {
+		let time_control = TimeControl{
+			base : seconds(10), bonus : TimeBonus::Increment(seconds(5))};
+		let mut clock = Clock::with_times(time_control, seconds(10), seconds(20));
+		let start = Instant::now();
+
+		clock.start_turn(1, start);
+		assert!(!clock.is_flagged(1, start + seconds(9)));
+		assert!(clock.is_flagged(1, start + seconds(10)));
+
+		// no increment once the time has run out
+		assert!(!clock.end_turn(start + seconds(12)));
+		assert_eq!(seconds(0), clock.remaining(1, start + seconds(12)));
+		assert_eq!(seconds(20), clock.remaining(2, start + seconds(12)));
+	}
+}
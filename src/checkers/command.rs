@@ -0,0 +1,214 @@
+use std::cmp;
+
+/// A command entered instead of a move
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Command {
+	Help,
+	Moves,
+	Board,
+	Undo,
+	Redo,
+	Save{ path : String },
+	Load{ path : String },
+	Flip,
+	Fen,
+	Resign,
+	Draw,
+	Accept,
+	Decline,
+	New,
+	Quit
+}
+
+/// The reasons a command is rejected
+#[derive(Debug, PartialEq, Eq)]
+pub enum CommandError {
+	/// There is no command `command`. The suggestions are the commands
+	/// it is the start of, or else the commands it is a typo of.
+	Unknown{ command : String, suggestions : Vec<&'static str> },
+
+	/// The command needs an argument, e.g. the file of `save`
+	MissingArgument{ command : &'static str },
+
+	/// The command does not take an argument
+	UnexpectedArgument{ command : &'static str }
+}
+
+/// The name of a command, the argument it takes,
+/// if any, and what it does
+pub struct CommandInfo {
+	pub name : &'static str,
+	pub argument : Option<&'static str>,
+	pub description : &'static str
+}
+
+/// All commands, in the order they are listed by `help`
+pub const COMMANDS : &'static [CommandInfo] = &[
+	CommandInfo{ name : "help", argument : None, description : "list the commands" },
+	CommandInfo{ name : "moves", argument : None, description : "list the legal moves" },
+	CommandInfo{ name : "board", argument : None, description : "show the board" },
+	CommandInfo{ name : "undo", argument : None, description : "take back the last move" },
+	CommandInfo{ name : "redo", argument : None, description : "make the move taken back again" },
+	CommandInfo{ name : "save", argument : Some("file"), description : "save the game" },
+	CommandInfo{ name : "load", argument : Some("file"), description : "load a saved game" },
+	CommandInfo{ name : "flip", argument : None, description : "turn the board around" },
+	CommandInfo{ name : "fen", argument : None, description : "show the position in FEN" },
+	CommandInfo{ name : "resign", argument : None, description : "concede the game" },
+	CommandInfo{ name : "draw", argument : None, description : "offer a draw" },
+	CommandInfo{ name : "accept", argument : None, description : "accept a draw offer" },
+	CommandInfo{ name : "decline", argument : None, description : "decline a draw offer" },
+	CommandInfo{ name : "new", argument : None, description : "start a new game" },
+	CommandInfo{ name : "quit", argument : None, description : "give up and leave" }];
+
+///
+/// Parse a command from a line of input, e.g. `moves` or `save game.pdn`.
+/// Commands are not case sensitive, and `q` is short for `quit`. Returns
+/// `None` if the line does not start with a word, e.g. for the move
+/// `a3 b4`, so that it can be parsed as a move instead.
+///
+pub fn parse_command(line : &str) -> Option<Result<Command, CommandError>> {
+	let line = line.trim();
+	let (word, argument) = match line.find(char::is_whitespace) {
+		Some(index) => (&line[..index], line[index..].trim()),
+		None => (line, "")
+	};
+
+	if word.is_empty() || !word.chars().all(|ch| ch.is_alphabetic()) {
+		return None;
+	}
+
+	let word = word.to_lowercase();
+	let name = if word == "q" { "quit" } else { &word[..] };
+	let info = match COMMANDS.iter().find(|info| info.name == name) {
+		Some(info) => info,
+		None => return Some(Err(CommandError::Unknown{
+			command : word.clone(),
+			suggestions : suggest_commands(&word)}))
+	};
+
+	match (info.argument, argument.is_empty()) {
+		(Some(_), true) =>
+			return Some(Err(CommandError::MissingArgument{command : info.name})),
+		(None, false) =>
+			return Some(Err(CommandError::UnexpectedArgument{command : info.name})),
+		_ => { }
+	}
+
+	let command = match info.name {
+		"help" => Command::Help,
+		"moves" => Command::Moves,
+		"board" => Command::Board,
+		"undo" => Command::Undo,
+		"redo" => Command::Redo,
+		"save" => Command::Save{path : argument.to_string()},
+		"load" => Command::Load{path : argument.to_string()},
+		"flip" => Command::Flip,
+		"fen" => Command::Fen,
+		"resign" => Command::Resign,
+		"draw" => Command::Draw,
+		"accept" => Command::Accept,
+		"decline" => Command::Decline,
+		"new" => Command::New,
+		"quit" => Command::Quit,
+		_ => unreachable!()
+	};
+
+	Some(Ok(command))
+}
+
+///
+/// The commands that start with `word`, as a shell would complete it,
+/// or else the commands that are at most two typos away from it
+///
+pub fn suggest_commands(word : &str) -> Vec<&'static str> {
+	let word = word.to_lowercase();
+	let completions : Vec<_> = COMMANDS.iter()
+		.map(|info| info.name)
+		.filter(|name| name.starts_with(&word[..]))
+		.collect();
+	if !completions.is_empty() {
+		return completions;
+	}
+
+	COMMANDS.iter()
+		.map(|info| info.name)
+		.filter(|name| edit_distance(name, &word) <= 2)
+		.collect()
+}
+
+// the number of characters to insert, delete or
+// replace to turn `from` into `to`
+fn edit_distance(from : &str, to : &str) -> usize {
+	let to : Vec<_> = to.chars().collect();
+	let mut previous : Vec<_> = (0..to.len() + 1).collect();
+	for (i, from_ch) in from.chars().enumerate() {
+		let mut current = vec![i + 1];
+		for (j, &to_ch) in to.iter().enumerate() {
+			let replace = previous[j] + if from_ch == to_ch { 0 } else { 1 };
+			let distance = cmp::min(replace, cmp::min(previous[j + 1], current[j]) + 1);
+			current.push(distance);
+		}
+		previous = current;
+	}
+
+	previous[to.len()]
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn test_command(line : &str, exp_command : Command) {
+		assert_eq!(Some(Ok(exp_command)), parse_command(line));
+	}
+
+	ptest!(test_command[
+		test_command_help("help", Command::Help),
+		test_command_moves(" moves ", Command::Moves),
+		test_command_upper_case("UNDO", Command::Undo),
+		test_command_q("q", Command::Quit),
+		test_command_capital_q("Q", Command::Quit),
+		test_command_save("save game.pdn", Command::Save{path : "game.pdn".to_string()}),
+		test_command_load_with_spaces("load  my game.pdn ",
+			Command::Load{path : "my game.pdn".to_string()})
+	]);
+
+	#[test]
+	fn moves_are_not_commands() {
+		assert_eq!(None, parse_command("a3 b4"));
+		assert_eq!(None, parse_command("b4"));
+		assert_eq!(None, parse_command("22x15"));
+		assert_eq!(None, parse_command(""));
+	}
+
+	#[test]
+	fn arguments_are_checked() {
+		assert_eq!(
+			Some(Err(CommandError::MissingArgument{command : "save"})),
+			parse_command("save"));
+		assert_eq!(
+			Some(Err(CommandError::UnexpectedArgument{command : "undo"})),
+			parse_command("undo twice"));
+	}
+
+	#[test]
+	fn unknown_commands_get_suggestions() {
+		assert_eq!(
+			Some(Err(CommandError::Unknown{
+				command : "d".to_string(),
+				suggestions : vec!["draw", "decline"]})),
+			parse_command("d"));
+		assert_eq!(vec!["resign"], suggest_commands("resing"));
+		assert_eq!(vec!["moves"], suggest_commands("mvoes"));
+		assert_eq!(vec!["board", "load"], suggest_commands("bord"));
+		assert!(suggest_commands("checkmate").is_empty());
+	}
+
+	#[test]
+	fn edit_distances() {
+		assert_eq!(0, edit_distance("undo", "undo"));
+		assert_eq!(1, edit_distance("undo", "undoo"));
+		assert_eq!(2, edit_distance("moves", "mvoes"));
+		assert_eq!(4, edit_distance("", "flip"));
+	}
+}
@@ -1,29 +1,182 @@
 extern crate std;
 
-use checkers::Board;
+use checkers::{Board, BoardPosition};
+use checkers::ai::Move;
+use checkers::notation::position_to_square;
 use checkers::piece::PieceType;
+use checkers::variant::Variant;
 use std::{char, io};
 use std::io::Write;
+use std::time::Duration;
 
-const EMPTY_PIECE_STR : &'static str = " ";
+const EMPTY_PIECE_SYMBOL : char = ' ';
 
-const RED_MAN_STR : &'static str = "r";
-const RED_KING_STR : &'static str = "R";
-const BLACK_MAN_STR : &'static str = "b";
-const BLACK_KING_STR : &'static str = "B";
+/// The name of the color a player's pieces are shown in, and the
+/// symbols used for their men and kings on the board
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlayerColor {
+	pub name : String,
+	pub man_symbol : char,
+	pub king_symbol : char
+}
+
+impl PlayerColor {
+	/// Creates a color shown with the first letter of `name`,
+	/// in lowercase for men and in uppercase for kings
+	///
+	/// # Panics
+	///
+	/// Panics if `name` is empty.
+	pub fn new(name : &str) -> PlayerColor {
+		let initial = match name.chars().next() {
+			Some(initial) => initial,
+			None => panic!("The name of a color cannot be empty")
+		};
+		
+		PlayerColor{
+			name : name.to_string(),
+			man_symbol : initial.to_lowercase().next().unwrap(),
+			king_symbol : initial.to_uppercase().next().unwrap()}
+	}
+}
+
+/// The colors of both players. Player 1 starts at the lowest
+/// ranks of the board and plays up the board, and player 2
+/// starts at the highest ranks and plays down the board.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColorScheme {
+	pub player1 : PlayerColor,
+	pub player2 : PlayerColor
+}
+
+impl ColorScheme {
+	pub fn new(player1 : PlayerColor, player2 : PlayerColor) -> ColorScheme {
+		ColorScheme{ player1 : player1, player2 : player2 }
+	}
+	
+	/// Red plays up the board and Black plays down the board
+	pub fn red_black() -> ColorScheme {
+		ColorScheme::new(PlayerColor::new("Red"), PlayerColor::new("Black"))
+	}
+	
+	/// The color of the player with ID `player_id`
+	///
+	/// # Panics
+	///
+	/// Panics if `player_id` is not 1 or 2.
+	pub fn color(&self, player_id : u32) -> &PlayerColor {
+		match player_id {
+			1 => &self.player1,
+			2 => &self.player2,
+			_ => panic!("No color for player {}", player_id)
+		}
+	}
+	
+	/// The name of the color of the player with ID `player_id`
+	///
+	/// # Panics
+	///
+	/// Panics if `player_id` is not 1 or 2.
+	pub fn name(&self, player_id : u32) -> &str {
+		&self.color(player_id).name
+	}
+	
+	/// Finds the ID of the player whose color has the case
+	/// insensitive name `name`
+	pub fn player_id(&self, name : &str) -> Option<u32> {
+		let name = name.to_lowercase();
+		if self.player1.name.to_lowercase() == name {
+			Some(1)
+		} else if self.player2.name.to_lowercase() == name {
+			Some(2)
+		} else {
+			None
+		}
+	}
+}
+
+/// Converts a zero-based column index to the name of its file.
+/// Files after `Z` use multiple letters: `AA`, `AB`, ..., `AZ`, `BA`, etc.
+pub fn column_to_file(column : usize) -> String {
+	let initial_file = 'A' as u32;
+	let alphabet_length = 26;
+
+	let mut file = Vec::new();
+	let mut remaining = column + 1;
+	while remaining > 0 {
+		let letter = (remaining - 1) % alphabet_length;
+		file.push(char::from_u32(initial_file + letter as u32).unwrap());
+		remaining = (remaining - 1) / alphabet_length;
+	}
+
+	file.into_iter().rev().collect()
+}
+
+/// Formats a board position as its file and rank, as it is
+/// entered in a move, e.g. `c3`
+pub fn format_position(position : BoardPosition) -> String {
+	format!("{}{}", column_to_file(position.column).to_lowercase(), position.row + 1)
+}
+
+/// How moves are written
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Notation {
+	/// Files and ranks, e.g. `c3 d4` or `a5 c3 e1`
+	Algebraic,
+
+	/// Numbered squares, e.g. `22-18` or `27x18x11`
+	Numeric
+}
+
+/// Formats `the_move` in `notation`, where the squares of a `size` by
+/// `size` board of `variant` are numbered as described by
+/// `position_to_square`
+pub fn format_move
+(the_move : &Move, notation : Notation, variant : Variant, size : usize)
+-> String {
+	match notation {
+		Notation::Algebraic => the_move.path().iter()
+			.map(|position| format_position(*position))
+			.collect::<Vec<_>>()
+			.join(" "),
+		Notation::Numeric => {
+			let separator = if the_move.is_jump() { "x" } else { "-" };
+			the_move.path().iter()
+				.map(|position| position_to_square(variant, size, *position).unwrap().to_string())
+				.collect::<Vec<_>>()
+				.join(separator)
+		}
+	}
+}
+
+/// Formats the time left on a clock as minutes and seconds, e.g.
+/// `4:05`, or as hours, minutes and seconds, e.g. `1:02:03`.
+/// Fractions of a second are rounded up, so that a clock
+/// only shows `0:00` once the time has run out.
+pub fn format_clock_time(time : Duration) -> String {
+	let partial_second = if time.subsec_nanos() > 0 { 1 } else { 0 };
+	let total_seconds = time.as_secs() + partial_second;
+	let hours = total_seconds / 3600;
+	let minutes = total_seconds / 60 % 60;
+	let seconds = total_seconds % 60;
+	
+	if hours > 0 {
+		format!("{}:{:02}:{:02}", hours, minutes, seconds)
+	} else {
+		format!("{}:{:02}", minutes, seconds)
+	}
+}
 
 fn print_justified_file
 <TWrite : Write>
-(writer : &mut TWrite, columns : usize, padding : usize)
+(writer : &mut TWrite, columns : &[usize], padding : usize)
 -> Result<(), std::io::Error> {
     for _ in 0..padding {
         try!(write!(writer, " "));
     }
 
-	let initial_file = 'A' as u32;
-    for c in 0..columns {
-        let file = char::from_u32(initial_file + c as u32).unwrap();
-        try!(write!(writer, "  {}", file));
+    for &c in columns {
+        try!(write!(writer, "{:>3}", column_to_file(c)));
     }
 
     try!(writeln!(writer, ""));
@@ -45,37 +198,44 @@ fn print_justified_rank
     Ok(())
 }
 
-pub fn print_board
+/// Prints the board as the player with ID `player_id` sees it, using
+/// the piece symbols of `colors`. Player 1 sees the lowest ranks at
+/// the bottom, and player 2 sees the board turned around.
+pub fn print_board_facing
 <TWrite : Write>
-(writer : &mut TWrite, board : &Board)
+(writer : &mut TWrite, board : &Board, colors : &ColorScheme, player_id : u32)
 -> Result<(), io::Error> {
-    let file_padding = board.number_columns().to_string().len();
     let rank_padding = board.number_rows().to_string().len();
+    let mut rows : Vec<_> = (0..board.number_rows()).rev().collect();
+    let mut columns : Vec<_> = (0..board.number_columns()).collect();
+    if player_id == 2 {
+        rows.reverse();
+        columns.reverse();
+    }
 
-    try!(print_justified_file(writer, board.number_columns(), file_padding));
+    try!(print_justified_file(writer, &columns, rank_padding));
 
-	for r in (0..board.number_rows()).rev() {
+	for &r in &rows {
         try!(print_justified_rank(writer, r + 1, rank_padding));
-		for c in 0..board.number_columns() {
+		for &c in &columns {
 			let tile = board.get_tile(r, c);
-			let piece_str = match tile.get_piece() {
-				None => EMPTY_PIECE_STR,
-				Some(piece) =>
-					match (piece.get_type(), piece.get_player_id()) {
-						(PieceType::Man, 1) => RED_MAN_STR,
-						(PieceType::King, 1) => RED_KING_STR,
-						(PieceType::Man, 2) => BLACK_MAN_STR,
-						(PieceType::King, 2) => BLACK_KING_STR,
-						_ => unreachable!()
+			let piece_symbol = match tile.get_piece() {
+				None => EMPTY_PIECE_SYMBOL,
+				Some(piece) => {
+					let color = colors.color(piece.get_player_id());
+					match piece.get_type() {
+						PieceType::Man => color.man_symbol,
+						PieceType::King => color.king_symbol
 					}
+				}
 			};
 			
-			try!(write!(writer, "[{}]", piece_str));
+			try!(write!(writer, "[{}]", piece_symbol));
 		}
 		try!(writeln!(writer, " {}", r + 1));
 	}
 
-    try!(print_justified_file(writer, board.number_columns(), file_padding));
+    try!(print_justified_file(writer, &columns, rank_padding));
 	Ok(())
 }
 
@@ -93,7 +253,7 @@ mod test {
 		let board = Board::new(1, 1);
 		
 		let mut result = Vec::<u8>::new();
-		print_board(&mut result, &board).unwrap();
+		print_board_facing(&mut result, &board, &ColorScheme::red_black(), 1).unwrap();
 		
 		let exp_result = "   A\n1 [ ] 1\n   A\n";
 		
@@ -105,7 +265,7 @@ mod test {
 		let board = Board::new(3, 3);
 		
 		let mut result = Vec::<u8>::new();
-		print_board(&mut result, &board).unwrap();
+		print_board_facing(&mut result, &board, &ColorScheme::red_black(), 1).unwrap();
 		
 		let exp_result = concat!(
 			"   A  B  C\n",
@@ -122,7 +282,7 @@ mod test {
 		let board = Board::new(5, 3);
 		
 		let mut result = Vec::<u8>::new();
-		print_board(&mut result, &board).unwrap();
+		print_board_facing(&mut result, &board, &ColorScheme::red_black(), 1).unwrap();
 		
 		let exp_result = concat!(
 			"   A  B  C\n",
@@ -154,7 +314,7 @@ mod test {
 		board.set_tile(0, 2, Box::new(OccupiedTile::new(Box::new(black_man))));
 		board.set_tile(4, 0, Box::new(OccupiedTile::new(Box::new(black_king))));
 		
-		print_board(&mut result, &board).unwrap();
+		print_board_facing(&mut result, &board, &ColorScheme::red_black(), 1).unwrap();
 
 		let exp_result = concat!(
 			"   A  B  C\n",
@@ -167,4 +327,163 @@ mod test {
 		
 		assert_eq!(exp_result.as_bytes(), &*result);
 	}
+	
+	#[test]
+	fn board_with_custom_colors() {
+		let mut result = Vec::<u8>::new();
+		
+		let black_player = Player{id : 1};
+		let white_player = Player{id : 2};
+		
+		let mut board = Board::new(2, 2);
+		
+		let black_man = ManPiece::new(&black_player);
+		let white_king = KingPiece::new(&white_player);
+		board.set_tile(0, 0, Box::new(OccupiedTile::new(Box::new(black_man))));
+		board.set_tile(1, 1, Box::new(OccupiedTile::new(Box::new(white_king))));
+		
+		let colors = ColorScheme::new(PlayerColor::new("Black"), PlayerColor::new("white"));
+		print_board_facing(&mut result, &board, &colors, 1).unwrap();
+		
+		let exp_result = concat!(
+			"   A  B\n",
+			"2 [ ][W] 2\n",
+			"1 [b][ ] 1\n",
+			"   A  B\n");
+		
+		assert_eq!(exp_result.as_bytes(), &*result);
+	}
+	
+	#[test]
+	fn board_facing_player2() {
+		let mut result = Vec::<u8>::new();
+		
+		let red_player = Player{id : 1};
+		let black_player = Player{id : 2};
+		
+		let mut board = Board::new(3, 2);
+		
+		let red_man = ManPiece::new(&red_player);
+		let black_king = KingPiece::new(&black_player);
+		board.set_tile(0, 0, Box::new(OccupiedTile::new(Box::new(red_man))));
+		board.set_tile(2, 1, Box::new(OccupiedTile::new(Box::new(black_king))));
+		
+		print_board_facing(&mut result, &board, &ColorScheme::red_black(), 2).unwrap();
+		
+		let exp_result = concat!(
+			"   B  A\n",
+			"1 [ ][r] 1\n",
+			"2 [ ][ ] 2\n",
+			"3 [B][ ] 3\n",
+			"   B  A\n");
+		
+		assert_eq!(exp_result.as_bytes(), &*result);
+	}
+	
+	#[test]
+	fn color_scheme_lookup() {
+		let colors = ColorScheme::red_black();
+		assert_eq!("Red", colors.name(1));
+		assert_eq!("Black", colors.name(2));
+		assert_eq!(Some(2), colors.player_id("black"));
+		assert_eq!(None, colors.player_id("white"));
+	}
+	
+	#[test]
+	fn empty_10x2_board() {
+		let board = Board::new(10, 2);
+		
+		let mut result = Vec::<u8>::new();
+		print_board_facing(&mut result, &board, &ColorScheme::red_black(), 1).unwrap();
+		
+		let exp_result = concat!(
+			"    A  B\n",
+			"10 [ ][ ] 10\n",
+			" 9 [ ][ ] 9\n",
+			" 8 [ ][ ] 8\n",
+			" 7 [ ][ ] 7\n",
+			" 6 [ ][ ] 6\n",
+			" 5 [ ][ ] 5\n",
+			" 4 [ ][ ] 4\n",
+			" 3 [ ][ ] 3\n",
+			" 2 [ ][ ] 2\n",
+			" 1 [ ][ ] 1\n",
+			"    A  B\n");
+		
+		assert_eq!(exp_result.as_bytes(), &*result);
+	}
+	
+	#[test]
+	fn empty_1x28_board() {
+		let board = Board::new(1, 28);
+		
+		let mut result = Vec::<u8>::new();
+		print_board_facing(&mut result, &board, &ColorScheme::red_black(), 1).unwrap();
+		
+		let files = "   A  B  C  D  E  F  G  H  I  J  K  L  M  N  O  P  Q  R  S  T  U  V  W  X  Y  Z AA AB\n";
+		let mut exp_result = files.to_string();
+		exp_result.push_str("1 ");
+		for _ in 0..28 {
+			exp_result.push_str("[ ]");
+		}
+		exp_result.push_str(" 1\n");
+		exp_result.push_str(files);
+		
+		assert_eq!(exp_result.as_bytes(), &*result);
+	}
+	
+	fn test_column_to_file(column : usize, exp_result : &str) {
+		assert_eq!(exp_result, column_to_file(column));
+	}
+	
+	fn test_format_position(position : BoardPosition, exp_result : &str) {
+		assert_eq!(exp_result, format_position(position));
+	}
+	
+	#[test]
+	fn format_moves_in_both_notations() {
+		let simple_move = Move::new(
+			vec![BoardPosition::new(2, 2), BoardPosition::new(3, 3)], vec![]);
+		assert_eq!("c3 d4",
+			format_move(&simple_move, Notation::Algebraic, Variant::American, 8));
+		assert_eq!("22-18",
+			format_move(&simple_move, Notation::Numeric, Variant::American, 8));
+
+		let jump_move = Move::new(
+			vec![BoardPosition::new(1, 5), BoardPosition::new(3, 3), BoardPosition::new(5, 5)],
+			vec![BoardPosition::new(2, 4), BoardPosition::new(4, 4)]);
+		assert_eq!("f2 d4 f6",
+			format_move(&jump_move, Notation::Algebraic, Variant::American, 8));
+		assert_eq!("27x18x11",
+			format_move(&jump_move, Notation::Numeric, Variant::American, 8));
+	}
+	
+	fn test_format_clock_time(time : Duration, exp_result : &str) {
+		assert_eq!(exp_result, format_clock_time(time));
+	}
+	
+	ptest!(test_format_clock_time [
+		test_format_clock_time_zero(Duration::new(0, 0), "0:00"),
+		test_format_clock_time_fraction(Duration::new(0, 1), "0:01"),
+		test_format_clock_time_minute(Duration::new(59, 999999999), "1:00"),
+		test_format_clock_time_minutes(Duration::from_secs(245), "4:05"),
+		test_format_clock_time_hours(Duration::from_secs(3723), "1:02:03")
+	]);
+	
+	ptest!(test_format_position [
+		test_format_position_a1(BoardPosition::new(0, 0), "a1"),
+		test_format_position_h8(BoardPosition::new(7, 7), "h8"),
+		test_format_position_l12(BoardPosition::new(11, 11), "l12"),
+		test_format_position_aa27(BoardPosition::new(26, 26), "aa27")
+	]);
+	
+	ptest!(test_column_to_file [
+		test_column_to_file_a(0, "A"),
+		test_column_to_file_z(25, "Z"),
+		test_column_to_file_aa(26, "AA"),
+		test_column_to_file_az(51, "AZ"),
+		test_column_to_file_ba(52, "BA"),
+		test_column_to_file_zz(701, "ZZ"),
+		test_column_to_file_aaa(702, "AAA")
+	]);
 }
\ No newline at end of file
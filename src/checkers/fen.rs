@@ -0,0 +1,254 @@
+use checkers::board::BoardPosition;
+use checkers::game::{Game, PositionBuilder, SetupError};
+use checkers::notation::{position_to_square, square_to_position};
+use checkers::piece::PieceType;
+use checkers::variant::Variant;
+
+/// The reasons a FEN string is rejected
+#[derive(Debug, PartialEq, Eq)]
+pub enum FenError {
+	/// The string is not of the form `W:W21,22,K30:B1,2,K5`
+	Malformed{ fen : String },
+
+	/// The square is not the number of a playable tile on the board
+	InvalidSquare{ square : String },
+
+	/// The pieces do not make a valid position
+	Setup(SetupError)
+}
+
+/// The ID of the player of `color`, `W` or `B`. White plays up
+/// the board as player 1, and Black plays down the board as player 2.
+pub fn color_to_player_id(color : &str) -> Option<u32> {
+	match color {
+		"W" | "w" => Some(1),
+		"B" | "b" => Some(2),
+		_ => None
+	}
+}
+
+/// The color of the player with ID `player_id`, as read
+/// by `color_to_player_id`
+pub fn player_id_to_color(player_id : u32) -> char {
+	if player_id == 1 { 'W' } else { 'B' }
+}
+
+// reads a square number such as `21`, or a range of
+// squares such as `21-32`
+fn parse_squares(squares : &str) -> Option<Vec<usize>> {
+	let mut bounds = squares.splitn(2, '-')
+		.map(|bound| bound.trim().parse::<usize>().ok());
+	let first = match bounds.next() {
+		Some(Some(first)) => first,
+		_ => return None
+	};
+	let last = match bounds.next() {
+		Some(Some(last)) if last >= first => last,
+		Some(_) => return None,
+		None => first
+	};
+
+	Some((first..last + 1).collect())
+}
+
+/// Sets up a game of `variant` on its regulation board from a FEN
+/// string such as `W:W21,22,K30:B1,2,K5`. The string starts with the
+/// color to move, followed by the squares of the white and the black
+/// pieces, where kings are marked with `K`. Squares are numbered as
+/// described by `position_to_square`, and consecutive squares can be
+/// given as a range, e.g. `W:W21-32:B1-12`. White is player 1, who
+/// plays up the board, and Black is player 2.
+pub fn read_fen(variant : Variant, fen : &str) -> Result<Game, FenError> {
+	let builder = try!(read_fen_position(variant, fen));
+	builder.build().map_err(FenError::Setup)
+}
+
+/// Reads a FEN string as `read_fen` does, without validating the
+/// position, so that more can be added to it before it is built
+pub fn read_fen_position(variant : Variant, fen : &str) -> Result<PositionBuilder, FenError> {
+	let malformed = || FenError::Malformed{fen : fen.to_string()};
+
+	let trimmed = fen.trim();
+	let trimmed = trimmed.trim_right_matches('.');
+	let mut fields = trimmed.split(':');
+
+	let side_to_move = try!(fields.next()
+		.and_then(|color| color_to_player_id(color.trim()))
+		.ok_or_else(&malformed));
+
+	let size = variant.board_size();
+	let mut builder = PositionBuilder::new(variant).side_to_move(side_to_move);
+	let mut colors_read = Vec::new();
+	for field in fields {
+		let field = field.trim();
+		let color = field.get(0..1).unwrap_or("");
+		let player_id = try!(color_to_player_id(color).ok_or_else(&malformed));
+		if colors_read.contains(&player_id) {
+			return Err(malformed());
+		}
+		colors_read.push(player_id);
+
+		let pieces = field[1..].split(',')
+			.map(|piece| piece.trim())
+			.filter(|piece| !piece.is_empty());
+		for piece in pieces {
+			let (piece_type, number) = if piece.starts_with('K') || piece.starts_with('k') {
+				(PieceType::King, &piece[1..])
+			} else {
+				(PieceType::Man, piece)
+			};
+
+			let invalid_square = || FenError::InvalidSquare{square : number.to_string()};
+			let squares = try!(parse_squares(number).ok_or_else(&invalid_square));
+			for square in squares {
+				let position = try!(square_to_position(variant, size, square)
+					.ok_or_else(&invalid_square));
+
+				builder = match piece_type {
+					PieceType::Man => builder.man(player_id, position),
+					PieceType::King => builder.king(player_id, position)
+				};
+			}
+		}
+	}
+
+	if colors_read.len() != 2 {
+		return Err(malformed());
+	}
+
+	Ok(builder)
+}
+
+/// Writes the current position of `game` as a FEN string, as
+/// read by `read_fen`
+pub fn write_fen(game : &Game) -> String {
+	let board = game.board();
+	let mut pieces = Vec::new();
+	for r in 0..board.number_rows() {
+		for c in 0..board.number_columns() {
+			if let Some(piece) = board.get_tile(r, c).get_piece() {
+				pieces.push((BoardPosition::new(r, c), piece.get_player_id(), piece.get_type()));
+			}
+		}
+	}
+
+	format_fen(game.variant(), board.number_rows(), game.current_player().id, &pieces)
+}
+
+/// Writes a FEN string for `pieces` on a `size` by `size` board of
+/// `variant`, where the player with ID `side_to_move` is to move
+pub fn format_fen
+(variant : Variant,
+		size : usize,
+		side_to_move : u32,
+		pieces : &[(BoardPosition, u32, PieceType)])
+-> String {
+	let mut fen = player_id_to_color(side_to_move).to_string();
+	for player_id in 1..3 {
+		let mut squares : Vec<_> = pieces.iter()
+			.filter(|&&(_, owner_id, _)| owner_id == player_id)
+			.map(|&(position, _, piece_type)|
+				(position_to_square(variant, size, position).unwrap(), piece_type))
+			.collect();
+		squares.sort_by_key(|&(square, _)| square);
+
+		let squares : Vec<_> = squares.into_iter()
+			.map(|(square, piece_type)| match piece_type {
+				PieceType::Man => square.to_string(),
+				PieceType::King => format!("K{}", square)
+			})
+			.collect();
+
+		fen.push(':');
+		fen.push(player_id_to_color(player_id));
+		fen.push_str(&squares.join(","));
+	}
+
+	fen
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use checkers::board::BoardPosition;
+	use checkers::game::{Game, SetupError};
+	use checkers::piece::PieceType;
+	use checkers::variant::Variant;
+
+	#[test]
+	fn read_position_with_kings() {
+		let game = read_fen(Variant::American, "W:W21,22,K30:B1,2,K5").unwrap();
+		assert_eq!(1, game.current_player().id);
+
+		let piece = game.board().get_tile(0, 2).get_piece().unwrap();
+		assert_eq!((1, PieceType::King), (piece.get_player_id(), piece.get_type()));
+		let piece = game.board().get_tile(6, 0).get_piece().unwrap();
+		assert_eq!((2, PieceType::King), (piece.get_player_id(), piece.get_type()));
+		let piece = game.board().get_tile(7, 1).get_piece().unwrap();
+		assert_eq!((2, PieceType::Man), (piece.get_player_id(), piece.get_type()));
+
+		let statistics = game.statistics();
+		assert_eq!(3, statistics.players[0].pieces());
+		assert_eq!(3, statistics.players[1].pieces());
+	}
+
+	#[test]
+	fn write_sorts_squares() {
+		let fen = "B:W18,24,27,28,K10,K15:B12,16,20,K22,K25,K29";
+		let game = read_fen(Variant::American, fen).unwrap();
+		assert_eq!(2, game.current_player().id);
+		assert_eq!("B:WK10,K15,18,24,27,28:B12,16,20,K22,K25,K29", write_fen(&game));
+	}
+
+	#[test]
+	fn read_ranges_of_squares() {
+		let game = read_fen(Variant::American, "B:W21-32:B1-12.").unwrap();
+		assert_eq!(2, game.current_player().id);
+		assert_eq!(
+			"B:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,12",
+			write_fen(&game));
+
+		let game = read_fen(Variant::American, "W:WK1-2,18:B5").unwrap();
+		assert_eq!("W:WK1,K2,18:B5", write_fen(&game));
+	}
+
+	#[test]
+	fn write_position_after_moves() {
+		let mut game = read_fen(Variant::American, "W:W21,K30:B1").unwrap();
+		let the_move = game.find_move(
+			&[BoardPosition::new(0, 2), BoardPosition::new(1, 3)]).unwrap();
+		game.apply(the_move).unwrap();
+		assert_eq!("B:W21,K26:B1", write_fen(&game));
+	}
+
+	#[test]
+	fn write_starting_position() {
+		let game = Game::new();
+		assert_eq!(
+			"W:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,12",
+			write_fen(&game));
+	}
+
+	#[test]
+	fn reject_bad_fen() {
+		assert_eq!(
+			Err(FenError::Malformed{fen : "X:W21:B1".to_string()}),
+			read_fen(Variant::American, "X:W21:B1").map(|_| ()));
+		assert_eq!(
+			Err(FenError::Malformed{fen : "W:W21".to_string()}),
+			read_fen(Variant::American, "W:W21").map(|_| ()));
+		assert_eq!(
+			Err(FenError::InvalidSquare{square : "33".to_string()}),
+			read_fen(Variant::American, "W:W33:B1").map(|_| ()));
+		assert_eq!(
+			Err(FenError::InvalidSquare{square : "32-21".to_string()}),
+			read_fen(Variant::American, "W:W32-21:B1").map(|_| ()));
+		assert_eq!(
+			Err(FenError::InvalidSquare{square : "30-33".to_string()}),
+			read_fen(Variant::American, "W:W30-33:B1").map(|_| ()));
+		assert_eq!(
+			Err(FenError::Setup(SetupError::Occupied{position : BoardPosition::new(2, 0)})),
+			read_fen(Variant::American, "W:W21:B21").map(|_| ()));
+	}
+}
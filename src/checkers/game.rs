@@ -3,31 +3,332 @@
 #[allow(unused_imports)]
 use checkers;
 
+use std::cmp;
+
+#[cfg(feature = "serialization")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serialization")]
+use serde::de::Error;
+
 use checkers::{
 	ai,
 	Board,
 	BoardPosition,
+	CapturePriority,
 	Direction,
+	GameEvent,
+	GameObserver,
+	GameStatistics,
+	Handicap,
 	JumpMove,
 	KingPiece,
+	ManPiece,
+	Move,
+	MoveVector,
 	OccupiedTile,
 	PieceType,
 	Player,
-	SimpleMove};
+	PlayerStatistics,
+	SimpleMove,
+	Tile,
+	Variant,
+	WinCondition};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
 pub enum GameState {
 	/// The game has not yet finished
 	InProgress,
 	
-	/// The game has finished. The `u32` is the ID of the winning player.
-	GameOver{ winner_id : u32 }
+	/// The game has finished. The `u32` is the ID of the winning player,
+	/// and `move_number` is the number of the last move made, where a
+	/// move number covers a move by each player, or 0 if no moves were
+	/// made.
+	GameOver{ winner_id : u32, reason : WinReason, move_number : usize },
+	
+	/// The game has finished without a winner after the
+	/// move numbered `move_number`
+	Draw{ reason : DrawReason, move_number : usize }
+}
+
+/// Why a game was won. When a player has no moves left, the player
+/// without moves loses, or in giveaway checkers wins.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub enum WinReason {
+	/// The player to move had no pieces left
+	AllPiecesCaptured,
+	
+	/// The player to move had pieces left, but none of them could move
+	AllPiecesBlocked,
+	
+	/// The losing player resigned
+	Resignation,
+	
+	/// The losing player ran out of time
+	Time
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub enum DrawReason {
+	/// The same position, with the same player to move,
+	/// occurred the number of times set by `DrawRules`
+	Repetition,
+	
+	/// Both players made the number of moves set by `DrawRules`
+	/// without capturing a piece or moving a man
+	MoveLimit,
+	
+	/// A player accepted the draw offered by their opponent
+	Agreement
+}
+
+/// Configures when a game is automatically drawn
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct DrawRules {
+	/// The number of times a position must occur for the game to be
+	/// drawn, or `None` to never draw by repetition
+	pub repetitions : Option<usize>,
+	
+	/// The number of moves each player may make without capturing a
+	/// piece or moving a man before the game is drawn, or `None` to
+	/// never draw by this rule
+	pub move_limit : Option<usize>
+}
+
+impl DrawRules {
+	/// Threefold repetition and the forty move rule
+	pub fn standard() -> DrawRules {
+		DrawRules{ repetitions : Some(3), move_limit : Some(40) }
+	}
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum MoveError {
-	InvalidMove,
-	ShouldHaveJumped
+	/// The move has fewer than two positions
+	IncompletePath,
+
+	/// The position is not on the board
+	OffBoard{ position : BoardPosition },
+
+	/// The position is a light tile, which pieces never occupy, or
+	/// a tile of the other color than the start of the move
+	LightTile{ position : BoardPosition },
+
+	/// There is no piece at the start of the move
+	EmptyStart{ position : BoardPosition },
+
+	/// The piece at the start of the move belongs to the opponent
+	OpponentPiece{ position : BoardPosition },
+
+	/// The step to the position is not diagonal, or not
+	/// orthogonal in Turkish draughts
+	InvalidDirection{ position : BoardPosition },
+
+	/// The piece cannot move to the position, e.g. because it is
+	/// occupied, or behind a man. If no move starts with the piece,
+	/// the position is the start of the move.
+	InvalidMove{ position : BoardPosition },
+
+	ShouldHaveJumped,
+	
+	/// The game has already finished
+	GameFinished
+}
+
+/// The reasons a position set up with a `PositionBuilder`,
+/// or a `Handicap`, is rejected
+#[derive(Debug, PartialEq, Eq)]
+pub enum SetupError {
+	/// No player in the game has the given ID. Player 1 starts at the
+	/// lowest ranks of the board and player 2 at the highest ranks.
+	UnknownPlayer{ player_id : u32 },
+	
+	/// The position is not on the board
+	OffBoard{ position : BoardPosition },
+	
+	/// The position is a tile that pieces may not stand on
+	/// in the variant, e.g. a light tile in American checkers
+	UnplayableTile{ position : BoardPosition },
+	
+	/// More than one piece was placed at the position
+	Occupied{ position : BoardPosition },
+	
+	/// A man was placed on the row where it would be crowned
+	ManOnCrowningRow{ position : BoardPosition },
+	
+	/// The player giving a handicap has no piece to remove at the position
+	MissingPiece{ position : BoardPosition },
+	
+	/// The player has more pieces than in the starting position
+	TooManyPieces{ player_id : u32 },
+	
+	/// The player has no pieces
+	NoPieces{ player_id : u32 },
+	
+	/// Moves have been made, so the starting position can no longer change
+	GameStarted,
+	
+	/// The game already has a handicap
+	HandicapGiven
+}
+
+/// Everything needed to restore a game: how it started, the moves made
+/// since, and how it ended, if it has. It is taken with `Game::snapshot`
+/// and restored with `Game::from_snapshot`, which replays the moves.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct GameSnapshot {
+	pub variant : Variant,
+	pub board_size : usize,
+	
+	/// The pieces on the board before the first move, and
+	/// the ID of the player who had the first turn
+	pub starting_pieces : Vec<(BoardPosition, u32, PieceType)>,
+	pub first_player_id : u32,
+	
+	pub handicap : Option<Handicap>,
+	pub draw_rules : DrawRules,
+	
+	/// The moves made so far, from the first move of the game
+	pub moves : Vec<Move>,
+	
+	/// The moves taken back by `undo`, in the order
+	/// `redo` makes them again
+	pub undone_moves : Vec<Move>,
+	
+	/// The ID of the player who offered a draw that
+	/// has not yet been accepted or declined
+	pub draw_offer : Option<u32>,
+	
+	/// The ID of the player to move
+	pub side_to_move : u32,
+	
+	pub state : GameState
+}
+
+/// The reasons a `GameSnapshot` cannot be restored
+#[derive(Debug, PartialEq, Eq)]
+pub enum SnapshotError {
+	/// The board size is odd, too small for each player to start with
+	/// a row of men, or larger than `MAX_BOARD_SIZE`
+	BoardSize{ size : usize },
+	
+	/// The starting pieces are not a position on the board
+	Setup(SetupError),
+	
+	/// The move numbered `index`, counting from 0, is illegal
+	IllegalMove{ index : usize },
+	
+	/// The undone move numbered `index`, counting from 0, is illegal
+	IllegalUndoneMove{ index : usize },
+	
+	/// The side to move, the draw offer or the state of
+	/// the game do not follow from the moves
+	Inconsistent
+}
+
+/// The reasons a resignation or draw offer can be refused
+#[derive(Debug, PartialEq, Eq)]
+pub enum ActionError {
+	/// The game has already finished
+	GameFinished,
+	
+	/// No player in the game has the given ID
+	UnknownPlayer,
+	
+	/// The player already offered a draw that has not been answered
+	DrawAlreadyOffered,
+	
+	/// The player's opponent offered a draw that has not been answered,
+	/// which the player must accept or decline instead of offering one
+	DrawOfferPending,
+	
+	/// The player's opponent has not offered a draw
+	NoDrawOffered
+}
+
+// A snapshot of the pieces on the board and the player to
+// move, used to detect repeated positions
+#[derive(PartialEq, Eq)]
+struct Position {
+	tiles : Vec<Option<(u32, PieceType)>>,
+	current_player_index : usize
+}
+
+// Everything needed to take back a move
+struct MoveRecord {
+	the_move : Move,
+	
+	// the owning player ID and type of each piece captured by the move
+	captured_pieces : Vec<(u32, PieceType)>,
+	
+	// whether the moving piece was crowned at the end of the move
+	crowned : bool,
+	
+	// the state of the game before the move
+	moves_without_progress : usize,
+	draw_offer : Option<u32>
+}
+
+// Ranks a jump sequence under the Italian capture priority
+// rules. The fields are compared in declaration order, and
+// a higher score means a higher priority.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct ItalianCaptureScore {
+	// the number of pieces captured
+	pieces : usize,
+	
+	// whether the capturing piece is a king
+	with_king : bool,
+	
+	// the number of kings captured
+	kings : usize,
+	
+	// larger when a king is captured earlier in the
+	// sequence, or zero when no kings are captured
+	earliest_king : usize
+}
+
+// Keeps only the jump sequences with the highest score
+fn retain_best_jumps<S, F>(moves : &mut Vec<JumpMove>, score : F)
+where S : Ord, F : Fn(&[BoardPosition]) -> S {
+	let best_score = moves.iter()
+		.flat_map(|jump_tree| jump_tree.jump_sequences().into_iter())
+		.map(|sequence| score(&sequence))
+		.max();
+	
+	for jump_tree in moves.iter_mut() {
+		jump_tree.retain_jump_sequences(
+			&|sequence| Some(score(sequence)) == best_score);
+	}
+	
+	moves.retain(|jump_tree| !jump_tree.jumps().is_empty());
+}
+
+// Creates a tile holding a new piece of the given type for a player
+fn new_piece_tile(player : &Player, piece_type : PieceType) -> Box<Tile> {
+	match piece_type {
+		PieceType::Man => Box::new(OccupiedTile::new(Box::new(ManPiece::new(player)))),
+		PieceType::King => Box::new(OccupiedTile::new(Box::new(KingPiece::new(player))))
+	}
+}
+
+// Counts the pieces belonging to a player on a board
+fn count_pieces(board : &Board, player_id : u32) -> usize {
+	let mut count = 0;
+	for r in 0..board.number_rows() {
+		for c in 0..board.number_columns() {
+			match board.get_tile(r, c).get_piece() {
+				Some(piece) if piece.get_player_id() == player_id => count += 1,
+				_ => {}
+			}
+		}
+	}
+	
+	count
 }
 
 struct PlayerInfo {
@@ -35,23 +336,74 @@ struct PlayerInfo {
 	direction : Direction
 }
 
+/// The largest board a game can be played on, whose files are `a` to `z`
+pub const MAX_BOARD_SIZE : usize = 26;
+
 pub struct Game {
+	variant : Variant,
 	players : [PlayerInfo; 2],
 	board : Board,
 	
 	current_player_index : usize,
+	state : GameState,
 	
 	available_simple_moves : Vec<SimpleMove>,
-	available_jump_moves : Vec<JumpMove>
+	available_jump_moves : Vec<JumpMove>,
+	
+	draw_rules : DrawRules,
+	
+	// the ID of the player who offered a draw that
+	// has not yet been accepted or declined
+	draw_offer : Option<u32>,
+	
+	// every position reached so far, including the current one
+	position_history : Vec<Position>,
+	
+	// the moves made so far, and the moves taken back
+	// by `undo` that can be made again by `redo`
+	move_history : Vec<MoveRecord>,
+	undone_moves : Vec<MoveRecord>,
+	
+	// the number of moves made by both players since the
+	// last capture or man move
+	moves_without_progress : usize,
+	
+	// the odds the game started with, if any
+	handicap : Option<Handicap>,
+	
+	observers : Vec<Box<GameObserver>>
 }
 
 impl Game {
+	/// Creates a new game of American checkers, which most tests play
+	#[cfg(test)]
 	pub fn new() -> Game {
+		Game::with_variant(Variant::American)
+	}
+	
+	/// Creates a new game in the starting position of `variant`
+	pub fn with_variant(variant : Variant) -> Game {
+		Game::with_board_size(
+			variant, variant.board_size(), variant.starting_rows())
+	}
+	
+	/// Creates a new game of `variant` on a `size` by `size` board,
+	/// where each player starts with `starting_rows` rows of men. For
+	/// example, a 6x6 American checkers board with two starting rows
+	/// can be used for teaching.
+	///
+	/// # Panics
+	///
+	/// Panics if `size` is odd, or if the starting rows of the two
+	/// players would overlap.
+	pub fn with_board_size
+	(variant : Variant, size : usize, starting_rows : usize)
+	-> Game {
 		let (player1, player2) = Game::create_two_players();
 		
-		let board = Board::new_checkerboard(&player1, &player2);
+		let board = variant.new_board(size, starting_rows, &player1, &player2);
 		
-		Game::with_board_and_players(board, player1, player2)
+		Game::with_board_and_players(variant, board, player1, player2)
 	}
 	
 	/// Creates a new Checkers game with an 8x8 board and the specified piece
@@ -77,7 +429,7 @@ impl Game {
 		Game::initialize_pieces(&mut board, &player1, &player1_positions);
 		Game::initialize_pieces(&mut board, &player2, &player2_positions);
 		
-		Game::with_board_and_players(board, player1, player2)
+		Game::with_board_and_players(Variant::American, board, player1, player2)
 	}
 	
 	// creates and returns two players with distinct IDs
@@ -99,7 +451,7 @@ impl Game {
 	}
 	
 	fn with_board_and_players
-	(board : Board, player1 : Player, player2 : Player)
+	(variant : Variant, board : Board, player1 : Player, player2 : Player)
 	-> Game {
 		let player1_info = PlayerInfo{
 			player : player1, direction : Direction::IncreasingRank};
@@ -107,17 +459,40 @@ impl Game {
 			player : player2, direction : Direction::DecreasingRank};
 		
 		let mut game = Game{
+			variant : variant,
 			players : [player1_info, player2_info],
 			board : board,
 			current_player_index : 0,
+			state : GameState::InProgress,
 			available_simple_moves : Vec::new(),
-			available_jump_moves : Vec::new()};
+			available_jump_moves : Vec::new(),
+			draw_rules : DrawRules::standard(),
+			draw_offer : None,
+			position_history : Vec::new(),
+			move_history : Vec::new(),
+			undone_moves : Vec::new(),
+			moves_without_progress : 0,
+			handicap : None,
+			observers : Vec::new()};
 			
 		game.find_available_moves();
 		
+		let position = game.current_position();
+		game.position_history.push(position);
+		
 		game
 	}
 	
+	// gives the first turn of the game to the player at `player_index`
+	fn start_with_player(&mut self, player_index : usize) {
+		self.current_player_index = player_index;
+		self.find_available_moves();
+		
+		let position = self.current_position();
+		self.position_history = vec![position];
+		self.state = self.find_state();
+	}
+	
 	fn current_player_info(&self) -> &PlayerInfo {
 		&self.players[self.current_player_index]
 	}
@@ -134,15 +509,14 @@ impl Game {
 				match self.board.get_tile(r, c).get_piece() {
 					Some(piece) =>
 						if piece.get_player_id() == curr_player_id {
-							let piece_moves = match piece.get_type() {
+							let movement = match piece.get_type() {
 								PieceType::Man =>
-									ai::find_simple_moves_for_man(
-										&self.board, curr_direction, r, c),
+									self.variant.man_movement(curr_direction),
 								PieceType::King =>
-									ai::find_simple_moves_for_king(
-										&self.board, r, c),
+									self.variant.king_movement(),
 							};
-							moves.extend(piece_moves);
+							moves.extend(ai::find_simple_moves(
+								&self.board, &movement, r, c));
 						},
 					None => {}
 				}
@@ -159,19 +533,20 @@ impl Game {
 		let curr_player = &curr_player_info.player;
 		let curr_player_id = curr_player.id;
 		let curr_direction = curr_player_info.direction;
+		let capture_rules = self.variant.capture_rules();
 		for r in 0..self.board.number_rows() {
 			for c in 0..self.board.number_columns() {
 				match self.board.get_tile(r, c).get_piece() {
 					Some(piece) =>
 						if piece.get_player_id() == curr_player_id {
-							let jump_move = match piece.get_type() {
+							let movement = match piece.get_type() {
 								PieceType::Man =>
-									ai::find_jump_moves_for_man(
-										&self.board, curr_player, curr_direction, r, c),
+									self.variant.man_movement(curr_direction),
 								PieceType::King =>
-									ai::find_jump_moves_for_king(
-										&self.board, curr_player, r, c),
+									self.variant.king_movement(),
 							};
+							let jump_move = ai::find_jump_moves(
+								&self.board, curr_player, &movement, &capture_rules, r, c);
 							if !jump_move.jumps().is_empty() {
 								moves.push(jump_move);
 							}
@@ -181,24 +556,358 @@ impl Game {
 			}
 		}
 		
+		self.apply_capture_priority(&mut moves);
+		
 		moves
 	}
 	
+	// removes the jumps that are not allowed by the
+	// capture priority rules of the variant being played
+	fn apply_capture_priority(&self, moves : &mut Vec<JumpMove>) {
+		match self.variant.capture_priority() {
+			CapturePriority::None => {},
+			CapturePriority::MostPieces =>
+				retain_best_jumps(moves, |sequence| sequence.len()),
+			CapturePriority::Italian =>
+				retain_best_jumps(moves, |sequence| self.italian_capture_score(sequence))
+		}
+	}
+	
+	fn italian_capture_score(&self, sequence : &[BoardPosition]) -> ItalianCaptureScore {
+		let start = sequence[0];
+		let capturing_type = self.board.get_tile(start.row, start.column)
+			.get_piece().unwrap().get_type();
+		
+		let captured_positions = ai::find_captured_positions(
+			&self.board, self.current_player(), sequence);
+		let captured_types : Vec<_> = captured_positions.iter()
+			.map(|position| self.board.get_tile(position.row, position.column)
+				.get_piece().unwrap().get_type())
+			.collect();
+		
+		let number_captured = captured_types.len();
+		let first_king = captured_types.iter()
+			.position(|piece_type| *piece_type == PieceType::King);
+		
+		ItalianCaptureScore{
+			pieces : number_captured,
+			with_king : capturing_type == PieceType::King,
+			kings : captured_types.iter()
+				.filter(|piece_type| **piece_type == PieceType::King)
+				.count(),
+			earliest_king : first_king
+				.map(|index| number_captured - index)
+				.unwrap_or(0)
+		}
+	}
+	
 	fn find_available_moves(&mut self) {
 		self.available_simple_moves = self.find_available_simple_moves();
 		self.available_jump_moves = self.find_available_jump_moves();
 	}
 	
+	pub fn variant(&self) -> Variant {
+		self.variant
+	}
+	
+	pub fn draw_rules(&self) -> DrawRules {
+		self.draw_rules
+	}
+	
+	/// Changes when the game is automatically drawn. The
+	/// standard rules are used unless this is called.
+	pub fn set_draw_rules(&mut self, draw_rules : DrawRules) {
+		self.draw_rules = draw_rules;
+	}
+	
+	/// Gives the first turn to the player with ID `player_id`, e.g.
+	/// player 2 when the player playing down the board moves first.
+	/// Player 1 moves first unless this is called.
+	///
+	/// # Panics
+	///
+	/// Panics if any moves have been made, or if no player
+	/// in the game has the ID `player_id`.
+	pub fn set_first_player(&mut self, player_id : u32) {
+		if !self.move_history.is_empty() {
+			panic!("The first player cannot change after moves are made")
+		}
+		
+		let player_index = match self.players.iter()
+				.position(|player_info| player_info.player.id == player_id) {
+			Some(player_index) => player_index,
+			None => panic!("No player has the ID {}", player_id)
+		};
+		
+		self.undone_moves.clear();
+		self.start_with_player(player_index);
+	}
+	
+	/// Starts the game with the odds of `handicap`: the stronger player's
+	/// pieces are removed and the weaker player's extra kings are placed.
+	/// The handicap is kept with the game, so that the game can be
+	/// recorded and replayed from the same position. The extra time is
+	/// given by the clock created with `Handicap::new_clock`.
+	pub fn set_handicap(&mut self, handicap : Handicap) -> Result<(), SetupError> {
+		if !self.move_history.is_empty() {
+			return Err(SetupError::GameStarted);
+		}
+		
+		if self.handicap.is_some() {
+			return Err(SetupError::HandicapGiven);
+		}
+		
+		let stronger_index = match self.players.iter()
+				.position(|player_info| player_info.player.id == handicap.player_id) {
+			Some(index) => index,
+			None => return Err(SetupError::UnknownPlayer{player_id : handicap.player_id})
+		};
+		let weaker_index = 1 - stronger_index;
+		
+		let weaker_player_id = handicap.weaker_player_id();
+		if self.players[weaker_index].player.id != weaker_player_id {
+			return Err(SetupError::UnknownPlayer{player_id : weaker_player_id});
+		}
+		
+		// the position is validated before the board is changed
+		let size = self.board.number_rows();
+		let mut removed = Vec::new();
+		for &position in &handicap.removed {
+			if position.row >= size || position.column >= size {
+				return Err(SetupError::OffBoard{position : position});
+			}
+			
+			let owned = self.board.get_tile(position.row, position.column).get_piece()
+				.map(|piece| piece.get_player_id() == handicap.player_id)
+				.unwrap_or(false);
+			if !owned || removed.contains(&position) {
+				return Err(SetupError::MissingPiece{position : position});
+			}
+			
+			removed.push(position);
+		}
+		
+		let mut placed = Vec::new();
+		for &position in &handicap.extra_kings {
+			if position.row >= size || position.column >= size {
+				return Err(SetupError::OffBoard{position : position});
+			}
+			
+			if !self.variant.is_playable_tile(position.row, position.column) {
+				return Err(SetupError::UnplayableTile{position : position});
+			}
+			
+			let occupied = self.board.get_tile(position.row, position.column)
+				.get_piece().is_some() && !removed.contains(&position);
+			if occupied || placed.contains(&position) {
+				return Err(SetupError::Occupied{position : position});
+			}
+			
+			placed.push(position);
+		}
+		
+		if count_pieces(&self.board, handicap.player_id) == removed.len() {
+			return Err(SetupError::NoPieces{player_id : handicap.player_id});
+		}
+		
+		for position in removed {
+			self.board.clear_tile(position.row, position.column);
+		}
+		
+		for position in placed {
+			let tile = new_piece_tile(&self.players[weaker_index].player, PieceType::King);
+			self.board.set_tile(position.row, position.column, tile);
+		}
+		
+		self.handicap = Some(handicap);
+		
+		let player_index = self.current_player_index;
+		self.undone_moves.clear();
+		self.start_with_player(player_index);
+		
+		Ok(())
+	}
+	
+	/// The odds the game started with, if any
+	pub fn handicap(&self) -> Option<&Handicap> {
+		self.handicap.as_ref()
+	}
+	
+	/// The pieces on the board when the game started, with the ID of
+	/// the player each belongs to
+	pub fn starting_pieces(&self) -> Vec<(BoardPosition, u32, PieceType)> {
+		let size = self.board.number_columns();
+		self.position_history[0].tiles.iter()
+			.enumerate()
+			.filter_map(|(index, tile)| tile.map(|(player_id, piece_type)|
+				(BoardPosition::new(index / size, index % size), player_id, piece_type)))
+			.collect()
+	}
+	
+	/// The ID of the player who had the first turn of the game
+	pub fn first_player_id(&self) -> u32 {
+		self.players[self.position_history[0].current_player_index].player.id
+	}
+	
+	/// The moves made so far, from the first move of the game
+	pub fn moves(&self) -> Vec<Move> {
+		self.move_history.iter()
+			.map(|record| record.the_move.clone())
+			.collect()
+	}
+	
+	/// Takes a snapshot of the game, from which it can be restored
+	pub fn snapshot(&self) -> GameSnapshot {
+		GameSnapshot{
+			variant : self.variant,
+			board_size : self.board.number_rows(),
+			starting_pieces : self.starting_pieces(),
+			first_player_id : self.first_player_id(),
+			handicap : self.handicap.clone(),
+			draw_rules : self.draw_rules,
+			moves : self.moves(),
+			undone_moves : self.undone_moves.iter().rev()
+				.map(|record| record.the_move.clone())
+				.collect(),
+			draw_offer : self.draw_offer,
+			side_to_move : self.current_player().id,
+			state : self.state.clone()}
+	}
+	
+	/// Restores a game from `snapshot` by setting up its starting
+	/// pieces and making its moves again. A game that ended by
+	/// resignation, on time or by agreement ends the same way.
+	pub fn from_snapshot(snapshot : GameSnapshot) -> Result<Game, SnapshotError> {
+		// the sizes `Game::with_board_size` accepts with at
+		// least one starting row, as with the `--size` option
+		let size = snapshot.board_size;
+		if size < 4 || size % 2 != 0 || size > MAX_BOARD_SIZE {
+			return Err(SnapshotError::BoardSize{size : size});
+		}
+		
+		// the starting rows of the game are not kept, so each player may
+		// have as many pieces as the most rows that fit on the board
+		let mut builder = PositionBuilder::with_board_size(snapshot.variant, size, size / 2 - 1)
+			.pieces(&snapshot.starting_pieces)
+			.side_to_move(snapshot.first_player_id);
+		if let Some(handicap) = snapshot.handicap {
+			builder = builder.handicap(handicap);
+		}
+		
+		// the draw rules are in force from the starting position
+		let mut game = try!(builder.build().map_err(SnapshotError::Setup));
+		game.draw_rules = snapshot.draw_rules;
+		let first_player_index = game.current_player_index;
+		game.start_with_player(first_player_index);
+		
+		for (index, the_move) in snapshot.moves.into_iter().enumerate() {
+			try!(game.apply(the_move).map_err(|_| SnapshotError::IllegalMove{index : index}));
+		}
+		
+		// making the undone moves and taking them back
+		// leaves them to be made again by `redo`
+		let number_undone = snapshot.undone_moves.len();
+		for (index, the_move) in snapshot.undone_moves.into_iter().enumerate() {
+			try!(game.apply(the_move)
+				.map_err(|_| SnapshotError::IllegalUndoneMove{index : index}));
+		}
+		for _ in 0..number_undone {
+			game.undo();
+		}
+		
+		// the moves do not show how a game ended that was not decided
+		// on the board, which ends after the last move by one of the
+		// players resigning, running out of time or agreeing to a draw
+		if game.state == GameState::InProgress {
+			let move_number = game.final_move_number();
+			match snapshot.state {
+				GameState::GameOver{winner_id, reason, ..} if (winner_id == 1 || winner_id == 2)
+						&& (reason == WinReason::Resignation || reason == WinReason::Time) =>
+					game.state = GameState::GameOver{
+						winner_id : winner_id, reason : reason, move_number : move_number},
+				GameState::Draw{reason : DrawReason::Agreement, ..} =>
+					game.state = GameState::Draw{
+						reason : DrawReason::Agreement, move_number : move_number},
+				_ => { }
+			}
+		}
+		
+		let valid_offer = match snapshot.draw_offer {
+			Some(player_id) => player_id == 1 || player_id == 2,
+			None => true
+		};
+		if game.state != snapshot.state || !valid_offer
+				|| game.current_player().id != snapshot.side_to_move {
+			return Err(SnapshotError::Inconsistent);
+		}
+		game.draw_offer = snapshot.draw_offer;
+		
+		Ok(game)
+	}
+	
+	pub fn state(&self) -> &GameState {
+		&self.state
+	}
+	
 	pub fn board(&self) -> &Board {
 		&self.board
 	}
 	
+	/// Reports the material left on the board, the captures made so far
+	/// and the number of moves made
+	pub fn statistics(&self) -> GameStatistics {
+		let mut players = [
+			PlayerStatistics{
+				player_id : self.players[0].player.id,
+				men : 0, kings : 0, captured : 0, longest_jump : 0},
+			PlayerStatistics{
+				player_id : self.players[1].player.id,
+				men : 0, kings : 0, captured : 0, longest_jump : 0}];
+		
+		for r in 0..self.board.number_rows() {
+			for c in 0..self.board.number_columns() {
+				if let Some(piece) = self.board.get_tile(r, c).get_piece() {
+					let player = players.iter_mut()
+						.find(|player| player.player_id == piece.get_player_id())
+						.unwrap();
+					match piece.get_type() {
+						PieceType::Man => player.men += 1,
+						PieceType::King => player.kings += 1
+					}
+				}
+			}
+		}
+		
+		for record in &self.move_history {
+			// the captured pieces all belong to the opponent of the capturer
+			if let Some(&(captured_id, _)) = record.captured_pieces.first() {
+				let number_captured = record.captured_pieces.len();
+				let player = players.iter_mut()
+					.find(|player| player.player_id != captured_id)
+					.unwrap();
+				player.captured += number_captured;
+				player.longest_jump = player.longest_jump.max(number_captured);
+			}
+		}
+		
+		let moves_made = self.move_history.len();
+		GameStatistics{
+			players : players,
+			moves_made : moves_made,
+			move_number : moves_made / 2 + 1,
+			moves_since_capture : self.move_history.iter().rev()
+				.take_while(|record| !record.the_move.is_jump())
+				.count()}
+	}
+	
 	pub fn current_player(&self) -> &Player {
 		&self.current_player_info().player
 	}
 	
+	// crowns the piece at the given position if it is a man that reached
+	// the far row, and returns whether it was crowned
 	fn check_for_coronation
-	(&mut self, row : usize, col : usize) {
+	(&mut self, row : usize, col : usize) -> bool {
 		let coronate = match self.board.get_tile(row, col).get_piece() {
 			Some(piece) =>
 				match piece.get_type() {
@@ -218,6 +927,8 @@ impl Game {
 			let tile = OccupiedTile::new(Box::new(king));
 			self.board.set_tile(row, col, Box::new(tile))
 		}
+		
+		coronate
 	}
 	
 	fn select_next_player(&mut self) {
@@ -234,80 +945,752 @@ impl Game {
 			&& self.available_jump_moves.is_empty()
 	}
 	
+	fn current_position(&self) -> Position {
+		let mut tiles = Vec::new();
+		for r in 0..self.board.number_rows() {
+			for c in 0..self.board.number_columns() {
+				let tile = self.board.get_tile(r, c).get_piece()
+					.map(|piece| (piece.get_player_id(), piece.get_type()));
+				tiles.push(tile);
+			}
+		}
+		
+		Position{
+			tiles : tiles,
+			current_player_index : self.current_player_index}
+	}
+	
+	fn find_draw(&self) -> Option<DrawReason> {
+		let position = self.position_history.last().unwrap();
+		let occurrences = self.position_history.iter()
+			.filter(|earlier_position| *earlier_position == position)
+			.count();
+		let repeated = self.draw_rules.repetitions
+			.map(|repetitions| occurrences >= repetitions)
+			.unwrap_or(false);
+		
+		let move_limit_reached = self.draw_rules.move_limit
+			.map(|move_limit| self.moves_without_progress >= 2 * move_limit)
+			.unwrap_or(false);
+		
+		if repeated {
+			Some(DrawReason::Repetition)
+		} else if move_limit_reached {
+			Some(DrawReason::MoveLimit)
+		} else {
+			None
+		}
+	}
+	
+	// starts the record of a move, before the board is changed
+	fn new_move_record(&self, the_move : Move) -> MoveRecord {
+		let captured_pieces = the_move.captured().iter()
+			.map(|position| {
+				let piece = self.board.get_tile(position.row, position.column)
+					.get_piece().unwrap();
+				(piece.get_player_id(), piece.get_type())
+			})
+			.collect();
+		
+		MoveRecord{
+			the_move : the_move,
+			captured_pieces : captured_pieces,
+			crowned : false,
+			moves_without_progress : self.moves_without_progress,
+			draw_offer : self.draw_offer}
+	}
+	
+	// `progress` is true when the move captured a piece or moved a man
 	fn finish_move
-	(&mut self, final_row : usize, final_col : usize)
+	(&mut self, mut record : MoveRecord, progress : bool)
 	-> GameState {
-		self.check_for_coronation(final_row, final_col);
+		let final_position = record.the_move.end();
+		record.crowned = self.check_for_coronation(
+			final_position.row, final_position.column);
+		self.move_history.push(record);
+		
+		// making a move instead of answering a draw offer declines it
+		let mover_id = self.current_player().id;
+		if self.draw_offer.is_some() && self.draw_offer != Some(mover_id) {
+			self.draw_offer = None;
+		}
 		
 		self.select_next_player();
 		self.find_available_moves();
 		
-		if self.is_game_over() {
-			let winner_id = self.players[1 - self.current_player_index].player.id;
-			GameState::GameOver{winner_id : winner_id}
+		if progress {
+			self.moves_without_progress = 0;
 		} else {
-			GameState::InProgress
+			self.moves_without_progress += 1;
 		}
+		
+		let position = self.current_position();
+		self.position_history.push(position);
+		
+		self.state = self.find_state();
+		self.notify_move(mover_id);
+		
+		self.state.clone()
 	}
 	
-	pub fn apply_simple_move(&mut self, the_move : SimpleMove) -> Result<GameState, MoveError> {
-		if self.available_jump_moves.is_empty() {
-			if self.available_simple_moves.contains(&the_move) {
-				self.board.swap_tiles(
-					the_move.from_row(),
-					the_move.from_column(),
-					the_move.to_row(),
-					the_move.to_column());
-				
-				let game_state = self.finish_move(
-					the_move.to_row(), the_move.to_column());
-				Ok(game_state)
-			} else {
-				Err(MoveError::InvalidMove)
-			}
-		} else {
-			Err(MoveError::ShouldHaveJumped)
+	/// Adds an observer that is told about every event
+	/// of the game from now on
+	pub fn add_observer(&mut self, observer : Box<GameObserver>) {
+		self.observers.push(observer);
+	}
+	
+	fn notify(&mut self, event : &GameEvent) {
+		for observer in self.observers.iter_mut() {
+			observer.on_event(event);
 		}
 	}
 	
-	pub fn apply_jump_move(&mut self, the_move : Vec<BoardPosition>) -> Result<GameState, MoveError> {
-		let jump_valid = self.available_jump_moves.iter()
-			.any(|jump_tree| jump_tree.contains_jump_sequence(&the_move));
-		if jump_valid {
-			let start_position = the_move.first().unwrap();
-			let final_position = the_move.last().unwrap();
-
-			// move the jumping piece
-			self.board.swap_tiles(
-				start_position.row,
-				start_position.column,
-				final_position.row,
-				final_position.column);
+	// tells the observers about the last move in the history
+	fn notify_move(&mut self, mover_id : u32) {
+		if self.observers.is_empty() {
+			return;
+		}
+		
+		let mut events = Vec::new();
+		{
+			let record = self.move_history.last().unwrap();
+			events.push(GameEvent::MoveMade{
+				player_id : mover_id, the_move : record.the_move.clone()});
 			
-			// remove all jumped pieces
-			let iter = the_move[0..].iter().zip(the_move[1..].iter());
-			for (jump_from_pos, jump_to_pos) in iter {
-				let jumped_row = (jump_from_pos.row + jump_to_pos.row) / 2;
-				let jumped_col = (jump_from_pos.column + jump_to_pos.column) / 2;
-				self.board.clear_tile(jumped_row, jumped_col);
+			if record.the_move.is_jump() {
+				events.push(GameEvent::PiecesCaptured{
+					player_id : mover_id, positions : record.the_move.captured().to_vec()});
 			}
-
-			let game_state = self.finish_move(
-				final_position.row, final_position.column);
-			Ok(game_state)
-		} else {
-			Err(MoveError::InvalidMove)
+			
+			if record.crowned {
+				events.push(GameEvent::PieceCrowned{
+					player_id : mover_id, position : record.the_move.end()});
+			}
+		}
+		
+		events.push(self.turn_event());
+		
+		for event in &events {
+			self.notify(event);
 		}
 	}
-}
-
-#[cfg(test)]
-mod test {
-	use super::*;
 	
-	use checkers::BoardPosition;
+	// the event describing whose turn it is, or how the game finished
+	fn turn_event(&self) -> GameEvent {
+		if self.state == GameState::InProgress {
+			GameEvent::TurnChanged{player_id : self.current_player().id}
+		} else {
+			GameEvent::GameOver{state : self.state.clone()}
+		}
+	}
+	
+	// finishes the game in `state` without a move being made
+	fn finish_game(&mut self, state : GameState) -> GameState {
+		self.state = state;
+		self.draw_offer = None;
+		
+		let event = self.turn_event();
+		self.notify(&event);
+		
+		self.state.clone()
+	}
+	
+	// determines the state of the game once the available moves and
+	// the position history are up to date for the current player
+	fn find_state(&self) -> GameState {
+		if self.is_game_over() {
+			let winner_index = match self.variant.win_condition() {
+				WinCondition::NoMovesLoses => 1 - self.current_player_index,
+				WinCondition::NoMovesWins => self.current_player_index
+			};
+			let winner_id = self.players[winner_index].player.id;
+			let reason = if count_pieces(&self.board, self.current_player().id) == 0 {
+				WinReason::AllPiecesCaptured
+			} else {
+				WinReason::AllPiecesBlocked
+			};
+			GameState::GameOver{
+				winner_id : winner_id, reason : reason, move_number : self.final_move_number()}
+		} else {
+			match self.find_draw() {
+				Some(reason) => GameState::Draw{
+					reason : reason, move_number : self.final_move_number()},
+				None => GameState::InProgress
+			}
+		}
+	}
+	
+	// the number of the last move made, where a move
+	// number covers a move by each player
+	fn final_move_number(&self) -> usize {
+		(self.move_history.len() + 1) / 2
+	}
+	
+	// finds the index of the player with the given ID, and checks that
+	// the game is still in progress so the player can take an action
+	fn action_player_index(&self, player_id : u32) -> Result<usize, ActionError> {
+		if self.state != GameState::InProgress {
+			return Err(ActionError::GameFinished);
+		}
+		
+		self.players.iter()
+			.position(|player_info| player_info.player.id == player_id)
+			.ok_or(ActionError::UnknownPlayer)
+	}
+	
+	/// The player with ID `player_id` resigns, and their opponent wins
+	pub fn resign(&mut self, player_id : u32) -> Result<GameState, ActionError> {
+		let player_index = try!(self.action_player_index(player_id));
+		
+		let winner_id = self.players[1 - player_index].player.id;
+		let move_number = self.final_move_number();
+		Ok(self.finish_game(GameState::GameOver{
+			winner_id : winner_id, reason : WinReason::Resignation, move_number : move_number}))
+	}
+	
+	/// The player with ID `player_id` ran out of time, and their
+	/// opponent wins. The game does not keep time itself; this is
+	/// called by whatever runs the players' clocks.
+	pub fn lose_on_time(&mut self, player_id : u32) -> Result<GameState, ActionError> {
+		let player_index = try!(self.action_player_index(player_id));
+		
+		let winner_id = self.players[1 - player_index].player.id;
+		let move_number = self.final_move_number();
+		Ok(self.finish_game(GameState::GameOver{
+			winner_id : winner_id, reason : WinReason::Time, move_number : move_number}))
+	}
+	
+	/// The ID of the player who offered a draw that has not
+	/// yet been answered, if there is one
+	pub fn draw_offer(&self) -> Option<u32> {
+		self.draw_offer
+	}
+	
+	/// The player with ID `player_id` offers their opponent a draw. The
+	/// offer stands until the opponent accepts it, declines it, or makes
+	/// a move.
+	pub fn offer_draw(&mut self, player_id : u32) -> Result<(), ActionError> {
+		try!(self.action_player_index(player_id));
+		
+		if self.draw_offer.is_some() {
+			return Err(if self.draw_offer == Some(player_id) {
+				ActionError::DrawAlreadyOffered
+			} else {
+				ActionError::DrawOfferPending
+			});
+		}
+		
+		self.draw_offer = Some(player_id);
+		Ok(())
+	}
+	
+	// checks that the opponent of the given player offered a draw
+	fn check_draw_offered_to(&self, player_id : u32) -> Result<(), ActionError> {
+		try!(self.action_player_index(player_id));
+		
+		match self.draw_offer {
+			Some(offering_id) if offering_id != player_id => Ok(()),
+			_ => Err(ActionError::NoDrawOffered)
+		}
+	}
+	
+	/// The player with ID `player_id` accepts the draw offered by their
+	/// opponent, and the game ends in a draw
+	pub fn accept_draw(&mut self, player_id : u32) -> Result<GameState, ActionError> {
+		try!(self.check_draw_offered_to(player_id));
+		
+		let move_number = self.final_move_number();
+		Ok(self.finish_game(GameState::Draw{
+			reason : DrawReason::Agreement, move_number : move_number}))
+	}
+	
+	/// The player with ID `player_id` declines the draw
+	/// offered by their opponent, and the game continues
+	pub fn decline_draw(&mut self, player_id : u32) -> Result<(), ActionError> {
+		try!(self.check_draw_offered_to(player_id));
+		
+		self.draw_offer = None;
+		Ok(())
+	}
+	
+	// puts a new piece of the given type for a player on the board
+	fn place_piece
+	(&mut self, position : BoardPosition, player_id : u32, piece_type : PieceType) {
+		let tile = {
+			let player = &self.players.iter()
+				.find(|player_info| player_info.player.id == player_id)
+				.unwrap().player;
+			new_piece_tile(player, piece_type)
+		};
+		self.board.set_tile(position.row, position.column, tile);
+	}
+	
+	/// Takes back the last move, restoring any captured pieces,
+	/// un-crowning a king crowned by the move, and giving the turn back
+	/// to the player who made it. If the game has finished, it is
+	/// resumed. Returns false if no moves have been made.
+	pub fn undo(&mut self) -> bool {
+		let record = match self.move_history.pop() {
+			Some(record) => record,
+			None => return false
+		};
+		
+		self.position_history.pop();
+		self.select_next_player();
+		
+		let start_position = record.the_move.start();
+		let final_position = record.the_move.end();
+		
+		if record.crowned {
+			let player_id = self.current_player().id;
+			self.place_piece(final_position, player_id, PieceType::Man);
+		}
+		
+		self.board.swap_tiles(
+			final_position.row,
+			final_position.column,
+			start_position.row,
+			start_position.column);
+		
+		let captured = record.the_move.captured().iter()
+			.zip(record.captured_pieces.iter());
+		for (&position, &(player_id, piece_type)) in captured {
+			self.place_piece(position, player_id, piece_type);
+		}
+		
+		self.moves_without_progress = record.moves_without_progress;
+		self.draw_offer = record.draw_offer;
+		self.state = GameState::InProgress;
+		self.find_available_moves();
+		
+		let events = [
+			GameEvent::MoveUndone{
+				player_id : self.current_player().id, the_move : record.the_move.clone()},
+			self.turn_event()];
+		for event in &events {
+			self.notify(event);
+		}
+		
+		self.undone_moves.push(record);
+		true
+	}
+	
+	/// Makes the last move taken back by `undo` again. Returns false if
+	/// there is no such move, or if the game has finished.
+	pub fn redo(&mut self) -> bool {
+		if self.state != GameState::InProgress || self.undone_moves.is_empty() {
+			return false;
+		}
+		
+		let record = self.undone_moves.pop().unwrap();
+		self.make_move(record.the_move);
+		
+		true
+	}
+	
+	/// Every legal move for the current player, or no moves if the game
+	/// has finished. When jumps are available only jump sequences are
+	/// included. If the variant does not require jumps to be completed,
+	/// sequences stopping part way through a multiple jump are included
+	/// as well as the complete sequences.
+	pub fn legal_moves(&self) -> Vec<Move> {
+		let mut moves = Vec::new();
+		
+		if self.state != GameState::InProgress {
+			return moves;
+		}
+		
+		if self.available_jump_moves.is_empty() {
+			for simple_move in &self.available_simple_moves {
+				let path = vec![
+					BoardPosition::new(simple_move.from_row(), simple_move.from_column()),
+					BoardPosition::new(simple_move.to_row(), simple_move.to_column())];
+				moves.push(Move::new(path, Vec::new()));
+			}
+		} else {
+			let complete_jumps = self.variant.requires_complete_jumps();
+			let sequences = self.available_jump_moves.iter()
+				.flat_map(|jump_tree| jump_tree.jump_sequences().into_iter());
+			for sequence in sequences {
+				let shortest = if complete_jumps { sequence.len() } else { 2 };
+				for length in shortest..sequence.len() + 1 {
+					let path = sequence[..length].to_vec();
+					let captured = ai::find_captured_positions(
+						&self.board, self.current_player(), &path);
+					let jump = Move::new(path, captured);
+					if !moves.contains(&jump) {
+						moves.push(jump);
+					}
+				}
+			}
+		}
+		
+		moves
+	}
+	
+	/// Finds the legal move whose piece visits the tiles in `path`,
+	/// starting with its origin
+	pub fn find_move(&self, path : &[BoardPosition]) -> Result<Move, MoveError> {
+		if self.state != GameState::InProgress {
+			return Err(MoveError::GameFinished);
+		}
+		
+		try!(self.check_path(path));
+		
+		let found = self.legal_moves().into_iter()
+			.find(|legal_move| legal_move.path() == path);
+		match found {
+			Some(legal_move) => Ok(legal_move),
+			None => {
+				let captures = !ai::find_captured_positions(
+					&self.board, self.current_player(), path).is_empty();
+				if self.available_jump_moves.is_empty() || captures {
+					Err(self.invalid_move(path))
+				} else {
+					Err(MoveError::ShouldHaveJumped)
+				}
+			}
+		}
+	}
+	
+	/// Makes `the_move`, which must be one of the moves
+	/// returned by `legal_moves`
+	pub fn apply(&mut self, the_move : Move) -> Result<GameState, MoveError> {
+		if self.state != GameState::InProgress {
+			return Err(MoveError::GameFinished);
+		}
+		
+		try!(self.check_path(the_move.path()));
+		
+		if self.legal_moves().contains(&the_move) {
+			self.undone_moves.clear();
+			Ok(self.make_move(the_move))
+		} else if !the_move.is_jump() && !self.available_jump_moves.is_empty() {
+			Err(MoveError::ShouldHaveJumped)
+		} else {
+			Err(self.invalid_move(the_move.path()))
+		}
+	}
+	
+	// makes the simple move `the_move`, as the tests
+	// written before moves had paths describe it
+	#[cfg(test)]
+	pub fn apply_simple_move(&mut self, the_move : SimpleMove) -> Result<GameState, MoveError> {
+		let path = vec![
+			BoardPosition::new(the_move.from_row(), the_move.from_column()),
+			BoardPosition::new(the_move.to_row(), the_move.to_column())];
+		self.apply_jump_move(path)
+	}
+	
+	// makes the move along `path`, which the tests use for jumps
+	#[cfg(test)]
+	pub fn apply_jump_move(&mut self, path : Vec<BoardPosition>) -> Result<GameState, MoveError> {
+		if self.state != GameState::InProgress {
+			return Err(MoveError::GameFinished);
+		}
+		
+		let the_move = try!(self.find_move(&path));
+		self.apply(the_move)
+	}
+	
+	// finds the move through the tiles at the rows and columns of `path`
+	#[cfg(test)]
+	pub fn find_move_through(&self, path : &[(usize, usize)]) -> Result<Move, MoveError> {
+		let path : Vec<_> = path.iter().map(|&(r, c)| BoardPosition::new(r, c)).collect();
+		self.find_move(&path)
+	}
+	
+	// finds and makes the move through the tiles at the rows and
+	// columns of `path`, which the tests use to play out games
+	#[cfg(test)]
+	pub fn play(&mut self, path : &[(usize, usize)]) {
+		let the_move = self.find_move_through(path).unwrap();
+		self.apply(the_move).unwrap();
+	}
+	
+	// checks that the tiles of `path` are on the board and can be
+	// reached one after the other by a piece of the current player
+	fn check_path(&self, path : &[BoardPosition]) -> Result<(), MoveError> {
+		if path.len() < 2 {
+			return Err(MoveError::IncompletePath);
+		}
+		
+		for &position in path {
+			if position.row >= self.board.number_rows()
+					|| position.column >= self.board.number_columns() {
+				return Err(MoveError::OffBoard{position : position});
+			}
+		}
+		
+		// a piece stays on tiles of the color it starts on, which are
+		// the dark tiles unless it was placed on a light tile
+		let start = path[0];
+		let dark_start = self.variant.is_playable_tile(start.row, start.column);
+		match self.board.get_tile(start.row, start.column).get_piece() {
+			None if dark_start => return Err(MoveError::EmptyStart{position : start}),
+			None => return Err(MoveError::LightTile{position : start}),
+			Some(piece) if piece.get_player_id() != self.current_player().id =>
+				return Err(MoveError::OpponentPiece{position : start}),
+			Some(_) => { }
+		}
+		
+		for &position in &path[1..] {
+			if self.variant.is_playable_tile(position.row, position.column) != dark_start {
+				return Err(MoveError::LightTile{position : position});
+			}
+		}
+		
+		let steps = self.variant.king_movement().steps;
+		for step in path.windows(2) {
+			if !is_in_direction(&steps, step[0], step[1]) {
+				return Err(MoveError::InvalidDirection{position : step[1]});
+			}
+		}
+		
+		Ok(())
+	}
+	
+	// the error for a `path` that passes `check_path` but is not
+	// a legal move, which is reported at the first position that
+	// no legal move visits after the same positions
+	fn invalid_move(&self, path : &[BoardPosition]) -> MoveError {
+		let matching = self.legal_moves().iter()
+			.map(|legal_move| legal_move.path().iter()
+				.zip(path)
+				.take_while(|&(legal, given)| legal == given)
+				.count())
+			.max()
+			.unwrap_or(0);
+		
+		MoveError::InvalidMove{position : path[cmp::min(matching, path.len() - 1)]}
+	}
+	
+	// makes a move that is known to be legal
+	fn make_move(&mut self, the_move : Move) -> GameState {
+		let start_position = the_move.start();
+		let final_position = the_move.end();
+		
+		let man_moved = self.board
+			.get_tile(start_position.row, start_position.column)
+			.get_piece().unwrap().get_type() == PieceType::Man;
+		let progress = man_moved || the_move.is_jump();
+		
+		let record = self.new_move_record(the_move);
+		
+		// move the piece
+		self.board.swap_tiles(
+			start_position.row,
+			start_position.column,
+			final_position.row,
+			final_position.column);
+		
+		// remove all jumped pieces
+		for position in record.the_move.captured() {
+			self.board.clear_tile(position.row, position.column);
+		}
+		
+		self.finish_move(record, progress)
+	}
+}
+
+// a game is serialized as its snapshot
+#[cfg(feature = "serialization")]
+impl Serialize for Game {
+	fn serialize<S : Serializer>(&self, serializer : S) -> Result<S::Ok, S::Error> {
+		self.snapshot().serialize(serializer)
+	}
+}
+
+#[cfg(feature = "serialization")]
+impl<'de> Deserialize<'de> for Game {
+	fn deserialize<D : Deserializer<'de>>(deserializer : D) -> Result<Game, D::Error> {
+		let snapshot = try!(GameSnapshot::deserialize(deserializer));
+		Game::from_snapshot(snapshot)
+			.map_err(|error| D::Error::custom(format!("cannot restore the game: {:?}", error)))
+	}
+}
+
+// whether a piece can get from `from` to `to` by repeating one of `steps`
+fn is_in_direction(steps : &[MoveVector], from : BoardPosition, to : BoardPosition) -> bool {
+	let rows = to.row as isize - from.row as isize;
+	let columns = to.column as isize - from.column as isize;
+	steps.iter().any(|step| {
+		let distance = if step.rows != 0 { rows / step.rows } else { columns / step.columns };
+		distance >= 1 && rows == distance * step.rows && columns == distance * step.columns
+	})
+}
+
+/// Sets up a game in any position, e.g. for analysis, puzzles and
+/// studies. Pieces are placed for player 1, who starts at the lowest
+/// ranks of the board, or player 2, who starts at the highest ranks.
+/// The position is validated when the game is built.
+pub struct PositionBuilder {
+	variant : Variant,
+	size : usize,
+	starting_rows : usize,
+	pieces : Vec<(BoardPosition, u32, PieceType)>,
+	side_to_move : u32,
+	handicap : Option<Handicap>
+}
+
+impl PositionBuilder {
+	/// Starts an empty position on the regulation board of `variant`,
+	/// with player 1 to move
+	pub fn new(variant : Variant) -> PositionBuilder {
+		PositionBuilder::with_board_size(
+			variant, variant.board_size(), variant.starting_rows())
+	}
+	
+	/// Starts an empty position on a `size` by `size` board, where each
+	/// player would start with `starting_rows` rows of men. No player
+	/// may have more pieces than in that starting position.
+	pub fn with_board_size
+	(variant : Variant, size : usize, starting_rows : usize)
+	-> PositionBuilder {
+		PositionBuilder{
+			variant : variant,
+			size : size,
+			starting_rows : starting_rows,
+			pieces : Vec::new(),
+			side_to_move : 1,
+			handicap : None}
+	}
+	
+	/// Places a man belonging to the player with ID `player_id`
+	pub fn man(mut self, player_id : u32, position : BoardPosition) -> PositionBuilder {
+		self.pieces.push((position, player_id, PieceType::Man));
+		self
+	}
+	
+	/// Places a king belonging to the player with ID `player_id`
+	pub fn king(mut self, player_id : u32, position : BoardPosition) -> PositionBuilder {
+		self.pieces.push((position, player_id, PieceType::King));
+		self
+	}
+	
+	/// Places each of `pieces`, given with the ID of the player it
+	/// belongs to, e.g. the starting pieces of another game
+	pub fn pieces(mut self, pieces : &[(BoardPosition, u32, PieceType)]) -> PositionBuilder {
+		self.pieces.extend_from_slice(pieces);
+		self
+	}
+	
+	/// Gives the first turn to the player with ID `player_id`
+	pub fn side_to_move(mut self, player_id : u32) -> PositionBuilder {
+		self.side_to_move = player_id;
+		self
+	}
+	
+	/// Records that the position was set up with the odds of `handicap`,
+	/// which are already on the board. The weaker player may have one
+	/// more piece than in the starting position for each extra king.
+	pub fn handicap(mut self, handicap : Handicap) -> PositionBuilder {
+		self.handicap = Some(handicap);
+		self
+	}
+	
+	/// Validates the position and creates a game starting from it
+	///
+	/// # Panics
+	///
+	/// Panics if the board size or the number of starting rows are not
+	/// accepted by `Game::with_board_size`.
+	pub fn build(self) -> Result<Game, SetupError> {
+		let (player1, player2) = Game::create_two_players();
+		let starting_board = self.variant.new_board(
+			self.size, self.starting_rows, &player1, &player2);
+		
+		let mut board = Board::new(self.size, self.size);
+		for &(position, player_id, piece_type) in &self.pieces {
+			let (player, crowning_row) = if player_id == player1.id {
+				(&player1, self.size - 1)
+			} else if player_id == player2.id {
+				(&player2, 0)
+			} else {
+				return Err(SetupError::UnknownPlayer{player_id : player_id});
+			};
+			
+			if position.row >= self.size || position.column >= self.size {
+				return Err(SetupError::OffBoard{position : position});
+			}
+			
+			if !self.variant.is_playable_tile(position.row, position.column) {
+				return Err(SetupError::UnplayableTile{position : position});
+			}
+			
+			if board.get_tile(position.row, position.column).get_piece().is_some() {
+				return Err(SetupError::Occupied{position : position});
+			}
+			
+			if piece_type == PieceType::Man && position.row == crowning_row {
+				return Err(SetupError::ManOnCrowningRow{position : position});
+			}
+			
+			board.set_tile(
+				position.row, position.column, new_piece_tile(player, piece_type));
+		}
+		
+		if let Some(ref handicap) = self.handicap {
+			if handicap.player_id != player1.id && handicap.player_id != player2.id {
+				return Err(SetupError::UnknownPlayer{player_id : handicap.player_id});
+			}
+		}
+		
+		for player in &[&player1, &player2] {
+			let number_pieces = count_pieces(&board, player.id);
+			if number_pieces == 0 {
+				return Err(SetupError::NoPieces{player_id : player.id});
+			}
+			
+			let extra_kings = match self.handicap {
+				Some(ref handicap) if handicap.weaker_player_id() == player.id =>
+					handicap.extra_kings.len(),
+				_ => 0
+			};
+			if number_pieces > count_pieces(&starting_board, player.id) + extra_kings {
+				return Err(SetupError::TooManyPieces{player_id : player.id});
+			}
+		}
+		
+		let first_player_index = if self.side_to_move == player1.id {
+			0
+		} else if self.side_to_move == player2.id {
+			1
+		} else {
+			return Err(SetupError::UnknownPlayer{player_id : self.side_to_move});
+		};
+		
+		let mut game = Game::with_board_and_players(self.variant, board, player1, player2);
+		game.handicap = self.handicap;
+		game.start_with_player(first_player_index);
+		
+		Ok(game)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	
+	use checkers::Board;
+	use checkers::BoardPosition;
+	use checkers::KingPiece;
+	use checkers::ManPiece;
+	use checkers::OccupiedTile;
 	use checkers::PieceType;
+	use checkers::Player;
 	use checkers::SimpleMove;
+	use checkers::Variant;
+	
+	fn place_man(board : &mut Board, player : &Player, row : usize, col : usize) {
+		let tile = OccupiedTile::new(Box::new(ManPiece::new(player)));
+		board.set_tile(row, col, Box::new(tile));
+	}
+	
+	fn place_king(board : &mut Board, player : &Player, row : usize, col : usize) {
+		let tile = OccupiedTile::new(Box::new(KingPiece::new(player)));
+		board.set_tile(row, col, Box::new(tile));
+	}
 
 	#[test]
 	fn good_simple_move() {
@@ -328,7 +1711,7 @@ mod test {
 	fn bad_simple_move() {
 		let mut game = Game::new();
 		let result = game.apply_simple_move(SimpleMove::new(2, 0, 3, 0));
-		let exp_result : Result<GameState, MoveError> = Err(MoveError::InvalidMove);
+		let exp_result : Result<GameState, MoveError> = Err(MoveError::LightTile{position : BoardPosition::new(3, 0)});
 		assert_eq!(exp_result, result);
 		
 		let player_id = game.current_player().id;
@@ -393,7 +1776,7 @@ mod test {
 		let mut game = Game::new();
 		let result = game.apply_jump_move(
 			vec![BoardPosition::new(2, 0), BoardPosition::new(4, 2)]);
-		let exp_result : Result<GameState, MoveError> = Err(MoveError::InvalidMove);
+		let exp_result : Result<GameState, MoveError> = Err(MoveError::InvalidMove{position : BoardPosition::new(4, 2)});
 		assert_eq!(exp_result, result);
 		
 		let player_id = game.current_player().id;
@@ -456,7 +1839,1122 @@ mod test {
 			vec![]);
 		
 		let result = game.apply_simple_move(SimpleMove::new(4, 4, 5, 5));
-		let exp_result : Result<GameState, MoveError> = Ok(GameState::GameOver{winner_id : 1});
+		let exp_result : Result<GameState, MoveError> = Ok(GameState::GameOver{
+			winner_id : 1, reason : WinReason::AllPiecesCaptured, move_number : 1});
 		assert_eq!(exp_result, result);
 	}
-}
\ No newline at end of file
+	
+	#[test]
+	fn italian_board_orientation() {
+		let game = Game::with_variant(Variant::Italian);
+		assert!(game.board().get_tile(0, 0).get_piece().is_none());
+		assert!(game.board().get_tile(0, 1).get_piece().is_some());
+		assert!(game.board().get_tile(7, 7).get_piece().is_none());
+		assert!(game.board().get_tile(7, 6).get_piece().is_some());
+	}
+	
+	#[test]
+	fn italian_men_cannot_capture_kings() {
+		let player1 = Player{id : 1};
+		let player2 = Player{id : 2};
+		let mut board = Board::new(8, 8);
+		place_man(&mut board, &player1, 2, 1);
+		place_king(&mut board, &player2, 3, 2);
+		let mut game = Game::with_board_and_players(
+			Variant::Italian, board, player1, player2);
+		
+		let result = game.apply_jump_move(
+			vec![BoardPosition::new(2, 1), BoardPosition::new(4, 3)]);
+		assert_eq!(Err(MoveError::InvalidMove{position : BoardPosition::new(4, 3)}), result);
+		
+		let result = game.apply_simple_move(SimpleMove::new(2, 1, 3, 0));
+		assert_eq!(Ok(GameState::InProgress), result);
+	}
+	
+	#[test]
+	fn italian_must_capture_most_pieces() {
+		let player1 = Player{id : 1};
+		let player2 = Player{id : 2};
+		let mut board = Board::new(8, 8);
+		place_man(&mut board, &player1, 2, 3);
+		place_man(&mut board, &player2, 3, 2);
+		place_man(&mut board, &player2, 3, 4);
+		place_man(&mut board, &player2, 5, 6);
+		let mut game = Game::with_board_and_players(
+			Variant::Italian, board, player1, player2);
+		
+		let result = game.apply_jump_move(
+			vec![BoardPosition::new(2, 3), BoardPosition::new(4, 1)]);
+		assert_eq!(Err(MoveError::InvalidMove{position : BoardPosition::new(4, 1)}), result);
+		
+		// the whole sequence must be taken
+		let result = game.apply_jump_move(
+			vec![BoardPosition::new(2, 3), BoardPosition::new(4, 5)]);
+		assert_eq!(Err(MoveError::InvalidMove{position : BoardPosition::new(4, 5)}), result);
+		
+		let result = game.apply_jump_move(
+			vec![
+				BoardPosition::new(2, 3),
+				BoardPosition::new(4, 5),
+				BoardPosition::new(6, 7)]);
+		assert_eq!(Ok(GameState::InProgress), result);
+		assert!(game.board().get_tile(3, 4).get_piece().is_none());
+		assert!(game.board().get_tile(5, 6).get_piece().is_none());
+	}
+	
+	#[test]
+	fn italian_must_capture_with_king() {
+		let player1 = Player{id : 1};
+		let player2 = Player{id : 2};
+		let mut board = Board::new(8, 8);
+		place_man(&mut board, &player1, 2, 1);
+		place_king(&mut board, &player1, 2, 5);
+		place_man(&mut board, &player2, 3, 2);
+		place_man(&mut board, &player2, 3, 6);
+		place_man(&mut board, &player2, 7, 0);
+		let mut game = Game::with_board_and_players(
+			Variant::Italian, board, player1, player2);
+		
+		let result = game.apply_jump_move(
+			vec![BoardPosition::new(2, 1), BoardPosition::new(4, 3)]);
+		assert_eq!(Err(MoveError::InvalidMove{position : BoardPosition::new(2, 1)}), result);
+		
+		let result = game.apply_jump_move(
+			vec![BoardPosition::new(2, 5), BoardPosition::new(4, 7)]);
+		assert_eq!(Ok(GameState::InProgress), result);
+	}
+	
+	#[test]
+	fn italian_must_capture_most_kings() {
+		let player1 = Player{id : 1};
+		let player2 = Player{id : 2};
+		let mut board = Board::new(8, 8);
+		place_king(&mut board, &player1, 3, 4);
+		place_man(&mut board, &player2, 4, 3);
+		place_king(&mut board, &player2, 4, 5);
+		let mut game = Game::with_board_and_players(
+			Variant::Italian, board, player1, player2);
+		
+		let result = game.apply_jump_move(
+			vec![BoardPosition::new(3, 4), BoardPosition::new(5, 2)]);
+		assert_eq!(Err(MoveError::InvalidMove{position : BoardPosition::new(5, 2)}), result);
+		
+		let result = game.apply_jump_move(
+			vec![BoardPosition::new(3, 4), BoardPosition::new(5, 6)]);
+		assert_eq!(Ok(GameState::InProgress), result);
+	}
+	
+	#[test]
+	fn american_allows_any_jump() {
+		let mut game = Game::with_piece_positions(
+			vec![BoardPosition::new(2, 3)],
+			vec![
+				BoardPosition::new(3, 2),
+				BoardPosition::new(3, 4),
+				BoardPosition::new(5, 6)]);
+		
+		let result = game.apply_jump_move(
+			vec![BoardPosition::new(2, 3), BoardPosition::new(4, 1)]);
+		assert_eq!(Ok(GameState::InProgress), result);
+	}
+	
+	#[test]
+	fn turkish_starting_position() {
+		let game = Game::with_variant(Variant::Turkish);
+		for c in 0..8 {
+			assert!(game.board().get_tile(0, c).get_piece().is_none());
+			assert!(game.board().get_tile(1, c).get_piece().is_some());
+			assert!(game.board().get_tile(2, c).get_piece().is_some());
+			assert!(game.board().get_tile(5, c).get_piece().is_some());
+			assert!(game.board().get_tile(6, c).get_piece().is_some());
+			assert!(game.board().get_tile(7, c).get_piece().is_none());
+		}
+	}
+	
+	#[test]
+	fn turkish_men_move_orthogonally() {
+		let player1 = Player{id : 1};
+		let player2 = Player{id : 2};
+		let mut board = Board::new(8, 8);
+		place_man(&mut board, &player1, 3, 3);
+		place_man(&mut board, &player2, 6, 6);
+		let mut game = Game::with_board_and_players(
+			Variant::Turkish, board, player1, player2);
+		
+		let result = game.apply_simple_move(SimpleMove::new(3, 3, 4, 4));
+		assert_eq!(Err(MoveError::InvalidDirection{position : BoardPosition::new(4, 4)}), result);
+		
+		let result = game.apply_simple_move(SimpleMove::new(3, 3, 2, 3));
+		assert_eq!(Err(MoveError::InvalidMove{position : BoardPosition::new(2, 3)}), result);
+		
+		let result = game.apply_simple_move(SimpleMove::new(3, 3, 3, 4));
+		assert_eq!(Ok(GameState::InProgress), result);
+	}
+	
+	#[test]
+	fn turkish_flying_king_must_capture_most_pieces() {
+		let player1 = Player{id : 1};
+		let player2 = Player{id : 2};
+		let mut board = Board::new(8, 8);
+		place_king(&mut board, &player1, 0, 0);
+		place_man(&mut board, &player2, 0, 4);
+		place_man(&mut board, &player2, 3, 6);
+		place_man(&mut board, &player2, 7, 7);
+		let mut game = Game::with_board_and_players(
+			Variant::Turkish, board, player1, player2);
+		
+		let result = game.apply_jump_move(
+			vec![BoardPosition::new(0, 0), BoardPosition::new(0, 5)]);
+		assert_eq!(Err(MoveError::InvalidMove{position : BoardPosition::new(0, 5)}), result);
+		
+		let result = game.apply_jump_move(
+			vec![
+				BoardPosition::new(0, 0),
+				BoardPosition::new(0, 6),
+				BoardPosition::new(5, 6)]);
+		assert_eq!(Ok(GameState::InProgress), result);
+		assert!(game.board().get_tile(0, 4).get_piece().is_none());
+		assert!(game.board().get_tile(3, 6).get_piece().is_none());
+		assert!(game.board().get_tile(5, 6).get_piece().is_some());
+	}
+	
+	fn count_pieces(game : &Game, player_id : u32) -> usize {
+		let board = game.board();
+		let mut count = 0;
+		for r in 0..board.number_rows() {
+			for c in 0..board.number_columns() {
+				let owned = board.get_tile(r, c).get_piece()
+					.map(|piece| piece.get_player_id() == player_id)
+					.unwrap_or(false);
+				if owned {
+					count += 1;
+				}
+			}
+		}
+		count
+	}
+	
+	#[test]
+	fn canadian_starting_position() {
+		let game = Game::with_variant(Variant::Canadian);
+		assert_eq!(12, game.board().number_rows());
+		assert_eq!(12, game.board().number_columns());
+		assert_eq!(30, count_pieces(&game, 1));
+		assert_eq!(30, count_pieces(&game, 2));
+		assert!(game.board().get_tile(0, 0).get_piece().is_some());
+		assert!(game.board().get_tile(11, 11).get_piece().is_some());
+	}
+	
+	#[test]
+	fn teaching_board_starting_position() {
+		let game = Game::with_board_size(Variant::American, 6, 2);
+		assert_eq!(6, game.board().number_rows());
+		assert_eq!(6, count_pieces(&game, 1));
+		assert_eq!(6, count_pieces(&game, 2));
+		assert!(game.board().get_tile(2, 0).get_piece().is_none());
+		assert!(game.board().get_tile(4, 0).get_piece().is_some());
+	}
+	
+	#[test]
+	#[should_panic]
+	fn odd_board_size() {
+		Game::with_board_size(Variant::American, 7, 2);
+	}
+	
+	#[test]
+	fn canadian_men_jump_backwards() {
+		let player1 = Player{id : 1};
+		let player2 = Player{id : 2};
+		let mut board = Board::new(12, 12);
+		place_man(&mut board, &player1, 4, 4);
+		place_man(&mut board, &player2, 3, 5);
+		place_man(&mut board, &player2, 11, 11);
+		let mut game = Game::with_board_and_players(
+			Variant::Canadian, board, player1, player2);
+		
+		let result = game.apply_jump_move(
+			vec![BoardPosition::new(4, 4), BoardPosition::new(2, 6)]);
+		assert_eq!(Ok(GameState::InProgress), result);
+		assert!(game.board().get_tile(3, 5).get_piece().is_none());
+	}
+	
+	#[test]
+	fn giveaway_player_without_moves_wins() {
+		let player1 = Player{id : 1};
+		let player2 = Player{id : 2};
+		let mut board = Board::new(8, 8);
+		place_man(&mut board, &player1, 3, 3);
+		place_man(&mut board, &player2, 4, 4);
+		let mut game = Game::with_board_and_players(
+			Variant::Giveaway, board, player1, player2);
+		
+		let result = game.apply_jump_move(
+			vec![BoardPosition::new(3, 3), BoardPosition::new(5, 5)]);
+		let exp_state = GameState::GameOver{
+			winner_id : 2, reason : WinReason::AllPiecesCaptured, move_number : 1};
+		assert_eq!(Ok(exp_state), result);
+	}
+	
+	fn lone_kings_game() -> Game {
+		let player1 = Player{id : 1};
+		let player2 = Player{id : 2};
+		let mut board = Board::new(8, 8);
+		place_king(&mut board, &player1, 0, 0);
+		place_king(&mut board, &player2, 7, 7);
+		Game::with_board_and_players(Variant::American, board, player1, player2)
+	}
+	
+	#[test]
+	fn draw_by_threefold_repetition() {
+		let mut game = lone_kings_game();
+		
+		// the starting position occurs for the second time after
+		// four moves, and for the third time after eight moves
+		let shuffle = [(0, 0, 1, 1), (7, 7, 6, 6), (1, 1, 0, 0), (6, 6, 7, 7)];
+		for &(from_row, from_col, to_row, to_col) in shuffle.iter().chain(shuffle[..3].iter()) {
+			let result = game.apply_simple_move(
+				SimpleMove::new(from_row, from_col, to_row, to_col));
+			assert_eq!(Ok(GameState::InProgress), result);
+		}
+		
+		let result = game.apply_simple_move(SimpleMove::new(6, 6, 7, 7));
+		let exp_state = GameState::Draw{reason : DrawReason::Repetition, move_number : 4};
+		assert_eq!(Ok(exp_state.clone()), result);
+		assert_eq!(exp_state, *game.state());
+	}
+	
+	#[test]
+	fn no_moves_after_draw() {
+		let mut game = lone_kings_game();
+		game.set_draw_rules(DrawRules{ repetitions : Some(1), move_limit : None });
+		
+		let result = game.apply_simple_move(SimpleMove::new(0, 0, 1, 1));
+		assert_eq!(Ok(GameState::Draw{reason : DrawReason::Repetition, move_number : 1}), result);
+		
+		let result = game.apply_simple_move(SimpleMove::new(7, 7, 6, 6));
+		assert_eq!(Err(MoveError::GameFinished), result);
+	}
+	
+	#[test]
+	fn draw_by_move_limit() {
+		let mut game = lone_kings_game();
+		game.set_draw_rules(DrawRules{ repetitions : None, move_limit : Some(1) });
+		
+		let result = game.apply_simple_move(SimpleMove::new(0, 0, 1, 1));
+		assert_eq!(Ok(GameState::InProgress), result);
+		
+		let result = game.apply_simple_move(SimpleMove::new(7, 7, 6, 6));
+		assert_eq!(Ok(GameState::Draw{reason : DrawReason::MoveLimit, move_number : 1}), result);
+	}
+	
+	#[test]
+	fn man_move_resets_move_limit() {
+		let player1 = Player{id : 1};
+		let player2 = Player{id : 2};
+		let mut board = Board::new(8, 8);
+		place_king(&mut board, &player1, 0, 0);
+		place_man(&mut board, &player2, 7, 7);
+		let mut game = Game::with_board_and_players(
+			Variant::American, board, player1, player2);
+		game.set_draw_rules(DrawRules{ repetitions : None, move_limit : Some(1) });
+		
+		let result = game.apply_simple_move(SimpleMove::new(0, 0, 1, 1));
+		assert_eq!(Ok(GameState::InProgress), result);
+		
+		let result = game.apply_simple_move(SimpleMove::new(7, 7, 6, 6));
+		assert_eq!(Ok(GameState::InProgress), result);
+	}
+	
+	#[test]
+	fn resignation() {
+		let mut game = Game::new();
+		
+		let result = game.resign(2);
+		let exp_state = GameState::GameOver{
+			winner_id : 1, reason : WinReason::Resignation, move_number : 0};
+		assert_eq!(Ok(exp_state.clone()), result);
+		assert_eq!(exp_state, *game.state());
+		
+		let result = game.apply_simple_move(SimpleMove::new(2, 0, 3, 1));
+		assert_eq!(Err(MoveError::GameFinished), result);
+		
+		assert_eq!(Err(ActionError::GameFinished), game.resign(1));
+	}
+	
+	#[test]
+	fn resignation_by_unknown_player() {
+		let mut game = Game::new();
+		assert_eq!(Err(ActionError::UnknownPlayer), game.resign(3));
+		assert_eq!(GameState::InProgress, *game.state());
+	}
+	
+	#[test]
+	fn draw_by_agreement() {
+		let mut game = Game::new();
+		
+		assert_eq!(Ok(()), game.offer_draw(1));
+		assert_eq!(Some(1), game.draw_offer());
+		assert_eq!(Err(ActionError::NoDrawOffered), game.accept_draw(1));
+		
+		let result = game.accept_draw(2);
+		let exp_state = GameState::Draw{reason : DrawReason::Agreement, move_number : 0};
+		assert_eq!(Ok(exp_state.clone()), result);
+		assert_eq!(exp_state, *game.state());
+	}
+	
+	#[test]
+	fn declined_draw_offer() {
+		let mut game = Game::new();
+		
+		assert_eq!(Ok(()), game.offer_draw(1));
+		assert_eq!(Err(ActionError::DrawAlreadyOffered), game.offer_draw(1));
+		assert_eq!(Ok(()), game.decline_draw(2));
+		assert_eq!(None, game.draw_offer());
+		assert_eq!(Err(ActionError::NoDrawOffered), game.accept_draw(2));
+		assert_eq!(GameState::InProgress, *game.state());
+	}
+	
+	#[test]
+	fn counter_offer_is_refused() {
+		let mut game = Game::new();
+		
+		game.offer_draw(1).unwrap();
+		assert_eq!(Err(ActionError::DrawOfferPending), game.offer_draw(2));
+		assert_eq!(Some(1), game.draw_offer());
+	}
+	
+	#[test]
+	fn draw_offer_stands_after_offering_players_move() {
+		let mut game = Game::new();
+		
+		game.offer_draw(1).unwrap();
+		game.apply_simple_move(SimpleMove::new(2, 0, 3, 1)).unwrap();
+		assert_eq!(Some(1), game.draw_offer());
+		
+		// the opponent moving instead of accepting declines the offer
+		game.apply_simple_move(SimpleMove::new(5, 1, 4, 0)).unwrap();
+		assert_eq!(None, game.draw_offer());
+	}
+	
+	#[test]
+	fn undo_without_moves() {
+		let mut game = Game::new();
+		assert!(!game.undo());
+		assert!(!game.redo());
+	}
+	
+	#[test]
+	fn undo_and_redo_simple_move() {
+		let mut game = Game::new();
+		game.apply_simple_move(SimpleMove::new(2, 0, 3, 1)).unwrap();
+		
+		assert!(game.undo());
+		assert_eq!(1, game.current_player().id);
+		assert!(game.board().get_tile(2, 0).get_piece().is_some());
+		assert!(game.board().get_tile(3, 1).get_piece().is_none());
+		assert!(!game.undo());
+		
+		assert!(game.redo());
+		assert_eq!(2, game.current_player().id);
+		assert!(game.board().get_tile(2, 0).get_piece().is_none());
+		assert!(game.board().get_tile(3, 1).get_piece().is_some());
+		assert!(!game.redo());
+	}
+	
+	#[test]
+	fn undo_jump_move_restores_captured_pieces() {
+		let mut game = Game::with_piece_positions(
+			vec![BoardPosition::new(3, 3)],
+			vec![
+				BoardPosition::new(4, 4),
+				BoardPosition::new(6, 4),
+				BoardPosition::new(2, 2)]);
+		
+		game.apply_jump_move(
+			vec![
+				BoardPosition::new(3, 3),
+				BoardPosition::new(5, 5),
+				BoardPosition::new(7, 3)]).unwrap();
+		assert!(game.undo());
+		
+		assert_eq!(1, game.current_player().id);
+		assert_eq!(1, game.board().get_tile(3, 3).get_piece().unwrap().get_player_id());
+		assert_eq!(2, game.board().get_tile(4, 4).get_piece().unwrap().get_player_id());
+		assert_eq!(2, game.board().get_tile(6, 4).get_piece().unwrap().get_player_id());
+		assert!(game.board().get_tile(7, 3).get_piece().is_none());
+		
+		// the jump is still required after taking it back
+		let result = game.apply_simple_move(SimpleMove::new(3, 3, 4, 2));
+		assert_eq!(Err(MoveError::ShouldHaveJumped), result);
+	}
+	
+	#[test]
+	fn undo_uncrowns_king() {
+		let mut game = Game::with_piece_positions(
+			vec![BoardPosition::new(6, 5)],
+			vec![BoardPosition::new(1, 1)]);
+		
+		game.apply_simple_move(SimpleMove::new(6, 5, 7, 4)).unwrap();
+		assert!(game.undo());
+		
+		let piece_type = game.board().get_tile(6, 5).get_piece().unwrap().get_type();
+		assert_eq!(PieceType::Man, piece_type);
+		
+		assert!(game.redo());
+		let piece_type = game.board().get_tile(7, 4).get_piece().unwrap().get_type();
+		assert_eq!(PieceType::King, piece_type);
+	}
+	
+	#[test]
+	fn new_move_discards_undone_moves() {
+		let mut game = Game::new();
+		game.apply_simple_move(SimpleMove::new(2, 0, 3, 1)).unwrap();
+		game.undo();
+		
+		game.apply_simple_move(SimpleMove::new(2, 2, 3, 3)).unwrap();
+		assert!(!game.redo());
+	}
+	
+	#[test]
+	fn undo_resumes_finished_game() {
+		let mut game = Game::with_piece_positions(
+			vec![BoardPosition::new(3, 3)],
+			vec![BoardPosition::new(4, 4)]);
+		
+		game.apply_jump_move(
+			vec![BoardPosition::new(3, 3), BoardPosition::new(5, 5)]).unwrap();
+		assert!(game.undo());
+		assert_eq!(GameState::InProgress, *game.state());
+		
+		assert!(game.redo());
+		let exp_state = GameState::GameOver{
+			winner_id : 1, reason : WinReason::AllPiecesCaptured, move_number : 1};
+		assert_eq!(exp_state, *game.state());
+	}
+	
+	#[test]
+	fn legal_moves_at_start() {
+		let game = Game::new();
+		let moves = game.legal_moves();
+		
+		// each of the four front men has two moves, except the one on the edge
+		assert_eq!(7, moves.len());
+		assert!(moves.iter().all(|legal_move| !legal_move.is_jump()));
+		
+		let exp_move = Move::new(
+			vec![BoardPosition::new(2, 0), BoardPosition::new(3, 1)], Vec::new());
+		assert!(moves.contains(&exp_move));
+	}
+	
+	#[test]
+	fn legal_moves_include_captured_positions() {
+		let game = Game::with_piece_positions(
+			vec![BoardPosition::new(3, 3)],
+			vec![
+				BoardPosition::new(4, 4),
+				BoardPosition::new(6, 4),
+				BoardPosition::new(2, 2)]);
+		
+		// American checkers allows stopping after the first jump
+		let exp_moves = vec![
+			Move::new(
+				vec![BoardPosition::new(3, 3), BoardPosition::new(5, 5)],
+				vec![BoardPosition::new(4, 4)]),
+			Move::new(
+				vec![
+					BoardPosition::new(3, 3),
+					BoardPosition::new(5, 5),
+					BoardPosition::new(7, 3)],
+				vec![BoardPosition::new(4, 4), BoardPosition::new(6, 4)])];
+		assert_eq!(exp_moves, game.legal_moves());
+	}
+	
+	#[test]
+	fn legal_moves_after_game_over() {
+		let mut game = Game::new();
+		game.resign(1).unwrap();
+		assert!(game.legal_moves().is_empty());
+	}
+	
+	#[test]
+	fn apply_legal_move() {
+		let mut game = Game::with_piece_positions(
+			vec![BoardPosition::new(3, 3)],
+			vec![
+				BoardPosition::new(4, 4),
+				BoardPosition::new(6, 4),
+				BoardPosition::new(2, 2)]);
+		
+		let the_move = game.find_move(&[
+			BoardPosition::new(3, 3),
+			BoardPosition::new(5, 5),
+			BoardPosition::new(7, 3)]).unwrap();
+		assert_eq!(Ok(GameState::InProgress), game.apply(the_move));
+		
+		assert!(game.board().get_tile(4, 4).get_piece().is_none());
+		assert!(game.board().get_tile(6, 4).get_piece().is_none());
+		assert!(game.board().get_tile(7, 3).get_piece().is_some());
+	}
+	
+	#[test]
+	fn apply_illegal_moves() {
+		let mut game = Game::with_piece_positions(
+			vec![BoardPosition::new(3, 3)],
+			vec![BoardPosition::new(4, 4)]);
+		
+		let simple_move = Move::new(
+			vec![BoardPosition::new(3, 3), BoardPosition::new(4, 2)], Vec::new());
+		assert_eq!(Err(MoveError::ShouldHaveJumped), game.apply(simple_move));
+		
+		// the captured positions must match the path
+		let jump = Move::new(
+			vec![BoardPosition::new(3, 3), BoardPosition::new(5, 5)],
+			vec![BoardPosition::new(4, 2)]);
+		assert_eq!(Err(MoveError::InvalidMove{position : BoardPosition::new(5, 5)}), game.apply(jump));
+	}
+	
+	#[test]
+	fn find_illegal_moves() {
+		let game = Game::with_piece_positions(
+			vec![BoardPosition::new(3, 3), BoardPosition::new(3, 1)],
+			vec![BoardPosition::new(4, 4)]);
+		
+		let result = game.find_move(&[BoardPosition::new(3, 1), BoardPosition::new(4, 0)]);
+		assert_eq!(Err(MoveError::ShouldHaveJumped), result);
+		
+		let result = game.find_move(&[BoardPosition::new(3, 3), BoardPosition::new(5, 5)]);
+		assert!(result.is_ok());
+		
+		let result = game.find_move(&[BoardPosition::new(3, 3), BoardPosition::new(5, 1)]);
+		assert_eq!(Err(MoveError::ShouldHaveJumped), result);
+	}
+	
+	#[test]
+	fn position_builder_places_pieces() {
+		let game = PositionBuilder::new(Variant::American)
+			.man(1, BoardPosition::new(2, 2))
+			.king(1, BoardPosition::new(7, 1))
+			.king(2, BoardPosition::new(4, 4))
+			.side_to_move(2)
+			.build().unwrap();
+		
+		assert_eq!(2, game.current_player().id);
+		assert_eq!(GameState::InProgress, *game.state());
+		
+		let piece = game.board().get_tile(7, 1).get_piece().unwrap();
+		assert_eq!(1, piece.get_player_id());
+		assert_eq!(PieceType::King, piece.get_type());
+		
+		let piece = game.board().get_tile(4, 4).get_piece().unwrap();
+		assert_eq!(2, piece.get_player_id());
+		assert_eq!(PieceType::King, piece.get_type());
+		
+		// the king of player 2 may move backwards
+		let the_move = Move::new(
+			vec![BoardPosition::new(4, 4), BoardPosition::new(5, 5)], Vec::new());
+		assert!(game.legal_moves().contains(&the_move));
+	}
+	
+	#[test]
+	fn position_builder_finds_blocked_side_to_move() {
+		let game = PositionBuilder::new(Variant::American)
+			.man(1, BoardPosition::new(0, 0))
+			.man(2, BoardPosition::new(1, 1))
+			.man(2, BoardPosition::new(2, 2))
+			.build().unwrap();
+		
+		let exp_state = GameState::GameOver{
+			winner_id : 2, reason : WinReason::AllPiecesBlocked, move_number : 0};
+		assert_eq!(exp_state, *game.state());
+	}
+	
+	#[test]
+	fn position_builder_rejects_invalid_positions() {
+		let result = PositionBuilder::new(Variant::American)
+			.man(1, BoardPosition::new(2, 1))
+			.man(2, BoardPosition::new(5, 1))
+			.build();
+		let exp_error = SetupError::UnplayableTile{position : BoardPosition::new(2, 1)};
+		assert_eq!(Some(exp_error), result.err());
+		
+		let result = PositionBuilder::new(Variant::American)
+			.man(1, BoardPosition::new(8, 0))
+			.build();
+		let exp_error = SetupError::OffBoard{position : BoardPosition::new(8, 0)};
+		assert_eq!(Some(exp_error), result.err());
+		
+		let result = PositionBuilder::new(Variant::American)
+			.man(1, BoardPosition::new(2, 0))
+			.king(2, BoardPosition::new(2, 0))
+			.build();
+		let exp_error = SetupError::Occupied{position : BoardPosition::new(2, 0)};
+		assert_eq!(Some(exp_error), result.err());
+		
+		let result = PositionBuilder::new(Variant::American)
+			.man(1, BoardPosition::new(2, 0))
+			.man(2, BoardPosition::new(0, 2))
+			.build();
+		let exp_error = SetupError::ManOnCrowningRow{position : BoardPosition::new(0, 2)};
+		assert_eq!(Some(exp_error), result.err());
+		
+		let result = PositionBuilder::new(Variant::American)
+			.man(3, BoardPosition::new(2, 0))
+			.build();
+		assert_eq!(Some(SetupError::UnknownPlayer{player_id : 3}), result.err());
+		
+		let result = PositionBuilder::new(Variant::American)
+			.man(1, BoardPosition::new(2, 0))
+			.man(2, BoardPosition::new(5, 1))
+			.side_to_move(3)
+			.build();
+		assert_eq!(Some(SetupError::UnknownPlayer{player_id : 3}), result.err());
+		
+		let result = PositionBuilder::new(Variant::American)
+			.man(1, BoardPosition::new(2, 0))
+			.build();
+		assert_eq!(Some(SetupError::NoPieces{player_id : 2}), result.err());
+	}
+	
+	#[test]
+	fn position_builder_limits_piece_counts() {
+		// a 6x6 board with two starting rows has six pieces per player
+		let mut builder = PositionBuilder::with_board_size(Variant::American, 6, 2)
+			.king(2, BoardPosition::new(5, 5));
+		for r in 0..3 {
+			for c in 0..3 {
+				builder = builder.man(1, BoardPosition::new(r, 2 * c + r % 2));
+			}
+		}
+		
+		assert_eq!(Some(SetupError::TooManyPieces{player_id : 1}), builder.build().err());
+	}
+	
+	#[test]
+	fn move_errors_name_the_position() {
+		let game = Game::new();
+		let find = |path : &[(usize, usize)]| game.find_move_through(path).err();
+		
+		assert_eq!(Some(MoveError::IncompletePath), find(&[(2, 0)]));
+		assert_eq!(
+			Some(MoveError::OffBoard{position : BoardPosition::new(12, 16383)}),
+			find(&[(2, 0), (12, 16383)]));
+		assert_eq!(
+			Some(MoveError::LightTile{position : BoardPosition::new(3, 0)}),
+			find(&[(3, 0), (4, 1)]));
+		assert_eq!(
+			Some(MoveError::LightTile{position : BoardPosition::new(3, 2)}),
+			find(&[(2, 2), (3, 2)]));
+		assert_eq!(
+			Some(MoveError::EmptyStart{position : BoardPosition::new(3, 1)}),
+			find(&[(3, 1), (4, 2)]));
+		assert_eq!(
+			Some(MoveError::OpponentPiece{position : BoardPosition::new(5, 1)}),
+			find(&[(5, 1), (4, 0)]));
+		assert_eq!(
+			Some(MoveError::InvalidDirection{position : BoardPosition::new(4, 0)}),
+			find(&[(2, 0), (4, 0)]));
+		assert_eq!(
+			Some(MoveError::InvalidMove{position : BoardPosition::new(4, 4)}),
+			find(&[(2, 2), (4, 4)]));
+		assert_eq!(
+			Some(MoveError::InvalidMove{position : BoardPosition::new(1, 1)}),
+			find(&[(1, 1), (2, 2)]));
+	}
+	
+	#[test]
+	fn second_player_moves_first() {
+		let mut game = Game::new();
+		game.set_first_player(2);
+		assert_eq!(2, game.current_player().id);
+		
+		let result = game.apply_simple_move(SimpleMove::new(2, 0, 3, 1));
+		assert_eq!(Err(MoveError::OpponentPiece{position : BoardPosition::new(2, 0)}), result);
+		
+		game.apply_simple_move(SimpleMove::new(5, 1, 4, 0)).unwrap();
+		assert_eq!(1, game.current_player().id);
+		
+		game.undo();
+		assert_eq!(2, game.current_player().id);
+	}
+	
+	#[test]
+	fn loss_on_time() {
+		let mut game = Game::new();
+		game.apply_simple_move(SimpleMove::new(2, 0, 3, 1)).unwrap();
+		
+		let result = game.lose_on_time(2);
+		let exp_state = GameState::GameOver{
+			winner_id : 1, reason : WinReason::Time, move_number : 1};
+		assert_eq!(Ok(exp_state), result);
+		assert!(game.legal_moves().is_empty());
+	}
+	
+	#[test]
+	fn observer_receives_events() {
+		use std::cell::RefCell;
+		use std::rc::Rc;
+		
+		let events = Rc::new(RefCell::new(Vec::new()));
+		let mut game = Game::with_piece_positions(
+			vec![BoardPosition::new(5, 3)],
+			vec![BoardPosition::new(6, 4), BoardPosition::new(1, 1)]);
+		
+		let observed_events = events.clone();
+		game.add_observer(Box::new(move |event : &GameEvent|
+			observed_events.borrow_mut().push(event.clone())));
+		
+		let jump = game.find_move(&[BoardPosition::new(5, 3), BoardPosition::new(7, 5)]).unwrap();
+		game.apply(jump.clone()).unwrap();
+		game.undo();
+		game.resign(1).unwrap();
+		
+		let exp_events = vec![
+			GameEvent::MoveMade{player_id : 1, the_move : jump.clone()},
+			GameEvent::PiecesCaptured{player_id : 1, positions : vec![BoardPosition::new(6, 4)]},
+			GameEvent::PieceCrowned{player_id : 1, position : BoardPosition::new(7, 5)},
+			GameEvent::TurnChanged{player_id : 2},
+			GameEvent::MoveUndone{player_id : 1, the_move : jump},
+			GameEvent::TurnChanged{player_id : 1},
+			GameEvent::GameOver{state : GameState::GameOver{
+				winner_id : 2, reason : WinReason::Resignation, move_number : 0}}];
+		assert_eq!(exp_events, *events.borrow());
+	}
+	
+	#[test]
+	fn statistics_at_start() {
+		let game = Game::new();
+		let statistics = game.statistics();
+		
+		assert_eq!(1, statistics.move_number);
+		assert_eq!(0, statistics.moves_made);
+		assert_eq!(0, statistics.moves_since_capture);
+		for player in &statistics.players {
+			assert_eq!(12, player.men);
+			assert_eq!(0, player.kings);
+			assert_eq!(0, player.captured);
+			assert_eq!(0, player.longest_jump);
+		}
+	}
+	
+	#[test]
+	fn statistics_count_captures() {
+		let mut game = Game::with_piece_positions(
+			vec![BoardPosition::new(3, 3)],
+			vec![
+				BoardPosition::new(4, 4),
+				BoardPosition::new(6, 4),
+				BoardPosition::new(6, 0)]);
+		
+		game.apply_jump_move(
+			vec![
+				BoardPosition::new(3, 3),
+				BoardPosition::new(5, 5),
+				BoardPosition::new(7, 3)]).unwrap();
+		game.apply_simple_move(SimpleMove::new(6, 0, 5, 1)).unwrap();
+		
+		let statistics = game.statistics();
+		assert_eq!(2, statistics.move_number);
+		assert_eq!(2, statistics.moves_made);
+		assert_eq!(1, statistics.moves_since_capture);
+		
+		let exp_player1 = PlayerStatistics{
+			player_id : 1, men : 0, kings : 1, captured : 2, longest_jump : 2};
+		assert_eq!(exp_player1, statistics.players[0]);
+		assert_eq!(1, statistics.players[1].pieces());
+		assert_eq!(0, statistics.players[1].captured);
+	}
+	
+	#[test]
+	fn game_over_when_all_pieces_blocked() {
+		let mut game = Game::with_piece_positions(
+			vec![BoardPosition::new(5, 1), BoardPosition::new(4, 2), BoardPosition::new(2, 2)],
+			vec![BoardPosition::new(6, 0)]);
+		
+		// the man on a7 can neither move to b6 nor jump it
+		let result = game.apply_simple_move(SimpleMove::new(2, 2, 3, 3));
+		let exp_state = GameState::GameOver{
+			winner_id : 1, reason : WinReason::AllPiecesBlocked, move_number : 1};
+		assert_eq!(Ok(exp_state), result);
+	}
+	
+	#[test]
+	fn handicap_removes_and_adds_pieces() {
+		let mut game = Game::new();
+		let handicap = Handicap::new(2)
+			.remove(BoardPosition::new(7, 1))
+			.remove(BoardPosition::new(5, 1))
+			.extra_king(BoardPosition::new(3, 3));
+		assert_eq!(Ok(()), game.set_handicap(handicap.clone()));
+		assert_eq!(Some(&handicap), game.handicap());
+		
+		assert!(game.board().get_tile(7, 1).get_piece().is_none());
+		assert!(game.board().get_tile(5, 1).get_piece().is_none());
+		let king = game.board().get_tile(3, 3).get_piece().unwrap();
+		assert_eq!((1, PieceType::King), (king.get_player_id(), king.get_type()));
+		
+		let statistics = game.statistics();
+		assert_eq!(10, statistics.players[1].pieces());
+		assert_eq!(13, statistics.players[0].pieces());
+		
+		// the extra king can move straight away
+		let result = game.apply_simple_move(SimpleMove::new(3, 3, 4, 4));
+		assert_eq!(Ok(GameState::InProgress), result);
+	}
+	
+	#[test]
+	fn handicap_is_validated() {
+		let removes_opponent = Handicap::new(1).remove(BoardPosition::new(7, 1));
+		assert_eq!(
+			Err(SetupError::MissingPiece{position : BoardPosition::new(7, 1)}),
+			Game::new().set_handicap(removes_opponent));
+		
+		let removes_twice = Handicap::new(1)
+			.remove(BoardPosition::new(0, 0))
+			.remove(BoardPosition::new(0, 0));
+		assert_eq!(
+			Err(SetupError::MissingPiece{position : BoardPosition::new(0, 0)}),
+			Game::new().set_handicap(removes_twice));
+		
+		let king_on_light_tile = Handicap::new(1).extra_king(BoardPosition::new(4, 1));
+		assert_eq!(
+			Err(SetupError::UnplayableTile{position : BoardPosition::new(4, 1)}),
+			Game::new().set_handicap(king_on_light_tile));
+		
+		let king_on_piece = Handicap::new(1).extra_king(BoardPosition::new(7, 1));
+		assert_eq!(
+			Err(SetupError::Occupied{position : BoardPosition::new(7, 1)}),
+			Game::new().set_handicap(king_on_piece));
+		
+		let unknown_player = Handicap::new(3);
+		assert_eq!(
+			Err(SetupError::UnknownPlayer{player_id : 3}),
+			Game::new().set_handicap(unknown_player));
+		
+		// nothing is changed when the handicap is rejected
+		let mut game = Game::new();
+		let off_board = Handicap::new(1)
+			.remove(BoardPosition::new(0, 0))
+			.extra_king(BoardPosition::new(8, 0));
+		assert_eq!(
+			Err(SetupError::OffBoard{position : BoardPosition::new(8, 0)}),
+			game.set_handicap(off_board));
+		assert!(game.board().get_tile(0, 0).get_piece().is_some());
+		assert_eq!(None, game.handicap());
+		
+		assert_eq!(Ok(()), game.set_handicap(Handicap::new(1)));
+		assert_eq!(Err(SetupError::HandicapGiven), game.set_handicap(Handicap::new(2)));
+		
+		let mut game = Game::new();
+		game.apply_simple_move(SimpleMove::new(2, 0, 3, 1)).unwrap();
+		assert_eq!(Err(SetupError::GameStarted), game.set_handicap(Handicap::new(1)));
+	}
+	
+	#[test]
+	fn position_builder_allows_extra_kings_of_handicap() {
+		let handicap = Handicap::new(2).extra_king(BoardPosition::new(3, 3));
+		let mut game = Game::new();
+		game.set_handicap(handicap.clone()).unwrap();
+		
+		let result = PositionBuilder::new(Variant::American)
+			.pieces(&game.starting_pieces())
+			.build();
+		assert_eq!(Some(SetupError::TooManyPieces{player_id : 1}), result.err());
+		
+		let built = PositionBuilder::new(Variant::American)
+			.pieces(&game.starting_pieces())
+			.handicap(handicap.clone())
+			.build().unwrap();
+		assert_eq!(Some(&handicap), built.handicap());
+		assert_eq!(game.starting_pieces(), built.starting_pieces());
+	}
+	
+	#[test]
+	fn snapshot_restores_game() {
+		let mut game = Game::new();
+		game.play(&[(2, 2), (3, 3)]);
+		game.play(&[(5, 1), (4, 0)]);
+		game.play(&[(2, 4), (3, 5)]);
+		game.undo();
+		game.offer_draw(1).unwrap();
+		
+		let snapshot = game.snapshot();
+		assert_eq!(1, snapshot.side_to_move);
+		assert_eq!(2, snapshot.moves.len());
+		assert_eq!(1, snapshot.undone_moves.len());
+		
+		let mut restored = Game::from_snapshot(snapshot.clone()).unwrap();
+		assert_eq!(snapshot, restored.snapshot());
+		assert_eq!(checkers::write_fen(&game), checkers::write_fen(&restored));
+		assert_eq!(Some(1), restored.draw_offer());
+		
+		// both games continue the same way
+		assert!(game.redo());
+		assert!(restored.redo());
+		game.play(&[(5, 5), (4, 4)]);
+		restored.play(&[(5, 5), (4, 4)]);
+		assert_eq!(game.snapshot(), restored.snapshot());
+		assert_eq!(game.legal_moves(), restored.legal_moves());
+	}
+	
+	#[test]
+	fn snapshot_restores_finished_games() {
+		let mut game = Game::new();
+		game.play(&[(2, 2), (3, 3)]);
+		game.resign(2).unwrap();
+		
+		let restored = Game::from_snapshot(game.snapshot()).unwrap();
+		assert_eq!(game.state(), restored.state());
+		
+		let mut game = Game::new();
+		game.set_handicap(Handicap::new(1).remove(BoardPosition::new(0, 0))).unwrap();
+		game.play(&[(2, 2), (3, 3)]);
+		game.offer_draw(2).unwrap();
+		game.accept_draw(1).unwrap();
+		
+		let restored = Game::from_snapshot(game.snapshot()).unwrap();
+		assert_eq!(game.snapshot(), restored.snapshot());
+		assert_eq!(game.handicap(), restored.handicap());
+		assert!(restored.board().get_tile(0, 0).get_piece().is_none());
+	}
+	
+	#[test]
+	fn snapshot_must_follow_from_moves() {
+		let mut game = Game::new();
+		game.play(&[(2, 2), (3, 3)]);
+		
+		let mut snapshot = game.snapshot();
+		snapshot.side_to_move = 1;
+		assert_eq!(Some(SnapshotError::Inconsistent), Game::from_snapshot(snapshot).err());
+		
+		let mut snapshot = game.snapshot();
+		snapshot.moves.push(snapshot.moves[0].clone());
+		snapshot.side_to_move = 1;
+		assert_eq!(
+			Some(SnapshotError::IllegalMove{index : 1}),
+			Game::from_snapshot(snapshot).err());
+		
+		let mut snapshot = game.snapshot();
+		snapshot.starting_pieces.push((BoardPosition::new(9, 9), 1, PieceType::Man));
+		assert_eq!(
+			Some(SnapshotError::Setup(SetupError::OffBoard{position : BoardPosition::new(9, 9)})),
+			Game::from_snapshot(snapshot).err());
+	}
+	
+	#[test]
+	fn snapshot_state_must_follow_from_moves() {
+		let mut game = Game::new();
+		game.play(&[(2, 2), (3, 3)]);
+		game.resign(2).unwrap();
+		
+		let mut snapshot = game.snapshot();
+		snapshot.state = GameState::GameOver{
+			winner_id : 7, reason : WinReason::Resignation, move_number : 1};
+		assert_eq!(Some(SnapshotError::Inconsistent), Game::from_snapshot(snapshot).err());
+		
+		let mut snapshot = game.snapshot();
+		snapshot.state = GameState::GameOver{
+			winner_id : 1, reason : WinReason::Time, move_number : 5};
+		assert_eq!(Some(SnapshotError::Inconsistent), Game::from_snapshot(snapshot).err());
+		
+		let mut snapshot = game.snapshot();
+		snapshot.state = GameState::Draw{reason : DrawReason::Agreement, move_number : 0};
+		assert_eq!(Some(SnapshotError::Inconsistent), Game::from_snapshot(snapshot).err());
+	}
+	
+	#[test]
+	fn snapshot_position_is_validated() {
+		let game = Game::new();
+		for &size in &[0, 2, 9, MAX_BOARD_SIZE + 2, usize::max_value()] {
+			let mut snapshot = game.snapshot();
+			snapshot.board_size = size;
+			assert_eq!(
+				Some(SnapshotError::BoardSize{size : size}),
+				Game::from_snapshot(snapshot).err());
+		}
+		
+		let mut snapshot = game.snapshot();
+		snapshot.starting_pieces.push((BoardPosition::new(3, 0), 1, PieceType::Man));
+		assert_eq!(
+			Some(SnapshotError::Setup(
+				SetupError::UnplayableTile{position : BoardPosition::new(3, 0)})),
+			Game::from_snapshot(snapshot).err());
+		
+		let mut snapshot = game.snapshot();
+		snapshot.starting_pieces.push((BoardPosition::new(4, 0), 1, PieceType::Man));
+		snapshot.starting_pieces.push((BoardPosition::new(4, 2), 1, PieceType::Man));
+		snapshot.starting_pieces.push((BoardPosition::new(4, 4), 1, PieceType::Man));
+		snapshot.starting_pieces.push((BoardPosition::new(4, 6), 1, PieceType::Man));
+		snapshot.starting_pieces.push((BoardPosition::new(3, 1), 1, PieceType::Man));
+		assert_eq!(
+			Some(SnapshotError::Setup(SetupError::TooManyPieces{player_id : 1})),
+			Game::from_snapshot(snapshot).err());
+		
+		let mut snapshot = game.snapshot();
+		snapshot.starting_pieces.retain(|&(position, _, _)| position != BoardPosition::new(7, 1));
+		snapshot.starting_pieces.push((BoardPosition::new(7, 1), 1, PieceType::Man));
+		assert_eq!(
+			Some(SnapshotError::Setup(
+				SetupError::ManOnCrowningRow{position : BoardPosition::new(7, 1)})),
+			Game::from_snapshot(snapshot).err());
+	}
+	
+	#[cfg(feature = "serialization")]
+	fn round_trip<T : Serialize + for<'de> Deserialize<'de>>(value : &T) -> T {
+		let json = serde_json::to_string(value).unwrap();
+		serde_json::from_str(&json).unwrap()
+	}
+	
+	#[cfg(feature = "serialization")]
+	#[test]
+	fn json_round_trip_of_game() {
+		let mut game = Game::with_variant(Variant::Italian);
+		game.play(&[(2, 1), (3, 2)]);
+		game.play(&[(5, 4), (4, 3)]);
+		game.undo();
+		
+		let mut restored : Game = round_trip(&game);
+		assert_eq!(game.snapshot(), restored.snapshot());
+		assert_eq!(GameState::InProgress, *restored.state());
+		
+		// the restored game continues identically
+		game.play(&[(5, 2), (4, 3)]);
+		restored.play(&[(5, 2), (4, 3)]);
+		assert_eq!(game.legal_moves(), restored.legal_moves());
+		assert_eq!(
+			serde_json::to_string(&game).unwrap(),
+			serde_json::to_string(&restored).unwrap());
+	}
+	
+	#[cfg(feature = "serialization")]
+	#[test]
+	fn json_round_trip_of_boards_and_moves() {
+		let game = Game::with_piece_positions(
+			vec![BoardPosition::new(3, 3)],
+			vec![BoardPosition::new(4, 4), BoardPosition::new(6, 4)]);
+		
+		let board : Board = round_trip(game.board());
+		assert_eq!(
+			serde_json::to_string(game.board()).unwrap(),
+			serde_json::to_string(&board).unwrap());
+		assert!(board.get_tile(4, 4).get_piece().is_some());
+		
+		let position = BoardPosition::new(3, 3);
+		assert_eq!(position, round_trip(&position));
+		
+		let simple_move = SimpleMove::new(2, 0, 3, 1);
+		assert_eq!(simple_move, round_trip(&simple_move));
+		
+		let jump_move = &game.available_jump_moves[0];
+		assert_eq!(*jump_move, round_trip(jump_move));
+		
+		let state = GameState::GameOver{
+			winner_id : 2, reason : WinReason::AllPiecesBlocked, move_number : 12};
+		assert_eq!(state, round_trip(&state));
+		
+		let board_json = "{\"rows\":2,\"columns\":2,\"tiles\":[null]}";
+		assert!(serde_json::from_str::<Board>(board_json).is_err());
+		
+		let board_json = "{\"rows\":4294967296,\"columns\":4294967296,\"tiles\":[]}";
+		assert!(serde_json::from_str::<Board>(board_json).is_err());
+	}
+}
@@ -0,0 +1,109 @@
+extern crate std;
+
+use std::time::Duration;
+
+use checkers::board::BoardPosition;
+use checkers::clock::{Clock, TimeControl};
+
+/// The odds given by the stronger player at the start of a game, to
+/// even out a game between players of different strength. The stronger
+/// player can start without some of their pieces, the weaker player can
+/// start with extra kings, and the weaker player can be given extra time.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct Handicap {
+	/// The ID of the stronger player, who gives the odds
+	pub player_id : u32,
+
+	/// The tiles of the pieces the stronger player starts without
+	pub removed : Vec<BoardPosition>,
+
+	/// The tiles where the weaker player starts with an extra king
+	pub extra_kings : Vec<BoardPosition>,
+
+	/// The time added to the weaker player's clock
+	pub extra_time : Duration
+}
+
+impl Handicap {
+	/// Starts a handicap given by the player with ID `player_id`,
+	/// which does not give any odds yet
+	pub fn new(player_id : u32) -> Handicap {
+		Handicap{
+			player_id : player_id,
+			removed : Vec::new(),
+			extra_kings : Vec::new(),
+			extra_time : Duration::new(0, 0)}
+	}
+
+	/// Removes the stronger player's piece at `position`
+	pub fn remove(mut self, position : BoardPosition) -> Handicap {
+		self.removed.push(position);
+		self
+	}
+
+	/// Gives the weaker player an extra king at `position`
+	pub fn extra_king(mut self, position : BoardPosition) -> Handicap {
+		self.extra_kings.push(position);
+		self
+	}
+
+	/// Adds `time` to the weaker player's clock
+	pub fn extra_time(mut self, time : Duration) -> Handicap {
+		self.extra_time = self.extra_time + time;
+		self
+	}
+
+	/// The ID of the weaker player, who receives the odds
+	pub fn weaker_player_id(&self) -> u32 {
+		if self.player_id == 1 { 2 } else { 1 }
+	}
+
+	/// Creates a stopped clock for `time_control` where the weaker
+	/// player starts with the extra time
+	pub fn new_clock(&self, time_control : TimeControl) -> Clock {
+		let base = time_control.base;
+		let odds = base + self.extra_time;
+		if self.player_id == 1 {
+			Clock::with_times(time_control, base, odds)
+		} else {
+			Clock::with_times(time_control, odds, base)
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use std::time::{Duration, Instant};
+
+	use checkers::board::BoardPosition;
+	use checkers::clock::{TimeBonus, TimeControl};
+
+	#[test]
+	fn handicap_collects_odds() {
+		let handicap = Handicap::new(2)
+			.remove(BoardPosition::new(7, 1))
+			.remove(BoardPosition::new(7, 3))
+			.extra_king(BoardPosition::new(0, 0))
+			.extra_time(Duration::from_secs(60))
+			.extra_time(Duration::from_secs(30));
+
+		assert_eq!(1, handicap.weaker_player_id());
+		assert_eq!(vec![BoardPosition::new(7, 1), BoardPosition::new(7, 3)], handicap.removed);
+		assert_eq!(vec![BoardPosition::new(0, 0)], handicap.extra_kings);
+		assert_eq!(Duration::from_secs(90), handicap.extra_time);
+	}
+
+	#[test]
+	fn weaker_player_starts_with_extra_time() {
+		let handicap = Handicap::new(1).extra_time(Duration::from_secs(120));
+		let clock = handicap.new_clock(
+			TimeControl{ base : Duration::from_secs(300), bonus : TimeBonus::None });
+		let now = Instant::now();
+
+		assert_eq!(Duration::from_secs(300), clock.remaining(1, now));
+		assert_eq!(Duration::from_secs(420), clock.remaining(2, now));
+	}
+}
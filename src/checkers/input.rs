@@ -1,9 +1,28 @@
+use checkers::ai::Move;
 use checkers::board::BoardPosition;
+use checkers::game::Game;
+use checkers::notation::{number_of_squares, square_to_position};
+use checkers::variant::Variant;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum InputError {
 	TooFewTokens,
-	InvalidTokens { tokens : Vec<TokenError> }
+	InvalidTokens { tokens : Vec<TokenError> },
+
+	/// The positions of a move are separated by both `-` and `x`
+	MixedMarkers,
+
+	/// The move is marked as a capture with `x`, but captures nothing
+	NotACapture,
+
+	/// The move captures pieces, but is marked with `-`
+	UnmarkedCapture,
+
+	/// No legal move ends at the single position given, or jumps from it
+	NoMatchingMove { position : BoardPosition },
+
+	/// The shorthand input matches more than one legal move
+	AmbiguousMove { candidates : Vec<Move> }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -11,15 +30,129 @@ pub enum TokenError {
 	MissingFile { token : String },
 	MissingRank { token : String },
 	ZeroRank { token : String },
-	InvalidCharacter { token : String, char_index : usize }
+	InvalidCharacter { token : String, char_index : usize },
+	SquareOutOfRange { token : String, max_square : usize },
+
+	/// The file or the rank is too large to be counted
+	PositionTooLarge { token : String }
+}
+
+/// How the positions of a move are separated in the input
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum CaptureMarker {
+	/// The positions are only separated by spaces, e.g. `a3 b4`
+	Unmarked,
+
+	/// The positions are separated by `-`, which marks a
+	/// move without captures, e.g. `a3-b4`
+	NoCapture,
+
+	/// The positions are separated by `x`, which marks
+	/// a capture, e.g. `a5xc3xe1`
+	Capture
 }
 
+/// A move read by `resolve_move`
+#[derive(Debug, PartialEq, Eq)]
+pub struct MoveInput {
+	pub positions : Vec<BoardPosition>,
+	pub marker : CaptureMarker
+}
+
+impl MoveInput {
+	/// Checks that the marker of the input agrees with `the_move`, the
+	/// move found for its positions
+	pub fn check_capture_marker(&self, the_move : &Move) -> Result<(), InputError> {
+		match (self.marker, the_move.is_jump()) {
+			(CaptureMarker::Capture, false) => Err(InputError::NotACapture),
+			(CaptureMarker::NoCapture, true) => Err(InputError::UnmarkedCapture),
+			_ => Ok(())
+		}
+	}
+}
+
+///
+/// Parse a move from a string in either algebraic notation, e.g. `a3 b4`,
+/// or numeric notation, e.g. `11-15`, `22x15` or `27x18x11`, where the
+/// playable tiles of the board of `game` are numbered as described by
+/// `position_to_square`. The positions may be separated by spaces, `-`
+/// or `x`, or not separated at all, e.g. `a3-b4`, `a5xc3xe1` or `a3b4`,
+/// and both notations may be mixed.
 ///
-/// Parse a move from a string
+/// Shorthand input is completed from the legal moves of `game`. A
+/// single position is taken as the destination of a move, or as the
+/// start of a jump, e.g. `b4` for `a3 b4`. The start and the end of a
+/// multiple jump are enough, e.g. `a5xe1` for `a5 c3 e1`, and where
+/// a multiple jump may stop early, shorthand for the jumps it passes
+/// through makes the complete jump. The positions of a completed move
+/// are those of the legal move they match, and other input is
+/// returned as read.
 ///
-pub fn parse_move(the_move : &str) -> Result<Vec<BoardPosition>, InputError> {
+pub fn resolve_move(the_move : &str, game : &Game) -> Result<MoveInput, InputError> {
+	let size = game.board().number_rows();
+	let input = try!(parse_positions(the_move, game.variant(), size, 1));
+
+	let legal_moves = game.legal_moves();
+	if legal_moves.iter().any(|legal_move| legal_move.path() == &input.positions[..]) {
+		return Ok(input);
+	}
+
+	let mut candidates : Vec<_> = legal_moves.into_iter()
+		.filter(|legal_move| matches_shorthand(&input.positions, legal_move))
+		.collect();
+
+	// where a multiple jump may stop early, the jumps it passes
+	// through match as well, and the complete jump is taken
+	let complete_jump = candidates.iter()
+		.position(|candidate| candidates.iter()
+			.all(|other| candidate.path().starts_with(other.path())));
+	if let Some(index) = complete_jump {
+		candidates = vec![candidates.swap_remove(index)];
+	}
+
+	match candidates.len() {
+		0 if input.positions.len() < 2 =>
+			Err(InputError::NoMatchingMove { position : input.positions[0] }),
+		0 => Ok(input),
+		1 => Ok(MoveInput {
+			positions : candidates.remove(0).path().to_vec(),
+			marker : input.marker
+		}),
+		_ => Err(InputError::AmbiguousMove { candidates : candidates })
+	}
+}
+
+// whether `positions` are a shorthand for `the_move`
+fn matches_shorthand(positions : &[BoardPosition], the_move : &Move) -> bool {
+	match positions.len() {
+		1 => the_move.end() == positions[0]
+			|| (the_move.is_jump() && the_move.start() == positions[0]),
+		_ => the_move.is_jump()
+			&& the_move.start() == positions[0]
+			&& the_move.end() == positions[positions.len() - 1]
+			&& positions.iter().all(|position| the_move.path().contains(position))
+	}
+}
+
+//
+// Reads the positions of a move, where numbered squares are those
+// of a `size` by `size` board of `variant`, and at least
+// `min_positions` positions must be given
+//
+fn parse_positions
+(the_move : &str, variant : Variant, size : usize, min_positions : usize)
+-> Result<MoveInput, InputError> {
 	let results : Vec<_> = the_move.split_whitespace()
-		.map(token_validator)
+		.map(|token| split_token(token).and_then(|(positions, markers)| {
+			let positions : Result<Vec<_>, _> = positions.into_iter()
+				.map(|position| if position.starts_with(is_rank_character) {
+					numbered_square(position, variant, size)
+				} else {
+					token_validator(position)
+				})
+				.collect();
+			positions.map(|positions| (positions, markers))
+		}))
 		.collect();
 
 	let (ok_iter, err_iter) : (Vec<_>, Vec<_>) = results.into_iter()
@@ -39,15 +172,99 @@ pub fn parse_move(the_move : &str) -> Result<Vec<BoardPosition>, InputError> {
 		return Err(InputError::InvalidTokens { tokens : errors });
 	}
 
-	let positions : Vec<_> = ok_iter.into_iter()
-		.filter_map(|position| position)
-		.collect();
+	let mut positions = Vec::new();
+	let mut markers = Vec::new();
+	for (token_positions, token_markers) in ok_iter.into_iter().filter_map(|token| token) {
+		positions.extend(token_positions);
+		markers.extend(token_markers);
+	}
 	
-	if positions.len() < 2 {
+	if positions.is_empty() || positions.len() < min_positions {
 		return Err(InputError::TooFewTokens);
 	}
 
-	Ok(positions)
+	let marker = match markers.first() {
+		Some(&marker) if markers.iter().any(|other| *other != marker) =>
+			return Err(InputError::MixedMarkers),
+		Some(&marker) => marker,
+		None => CaptureMarker::Unmarked
+	};
+
+	Ok(MoveInput { positions : positions, marker : marker })
+}
+
+enum SplitState {
+	// between positions
+	Separator,
+	File,
+	Rank,
+	// a numbered square
+	Square
+}
+
+//
+// Splits a token into the strings of its positions and the markers
+// between them. A position starts with a letter after a rank, e.g.
+// `a3b4`, and an `x` after a rank or square marks a capture, e.g.
+// `a5xc3`, while an `x` at the start of a position names a file.
+//
+fn split_token(token : &str) -> Result<(Vec<&str>, Vec<CaptureMarker>), TokenError> {
+	let invalid_character = |char_index| TokenError::InvalidCharacter {
+		token : token.to_string(), char_index : char_index };
+
+	let mut positions = Vec::new();
+	let mut markers = Vec::new();
+	let mut state = SplitState::Separator;
+	let mut start = 0;
+	let mut last_index = 0;
+
+	for (char_index, (byte_index, ch)) in token.char_indices().enumerate() {
+		last_index = char_index;
+		state = match state {
+			SplitState::Rank | SplitState::Square if ch == 'x' || ch == 'X' => {
+				positions.push(&token[start..byte_index]);
+				markers.push(CaptureMarker::Capture);
+				SplitState::Separator
+			},
+			SplitState::File | SplitState::Rank | SplitState::Square if ch == '-' => {
+				positions.push(&token[start..byte_index]);
+				markers.push(CaptureMarker::NoCapture);
+				SplitState::Separator
+			},
+			SplitState::Separator if is_file_character(ch) => {
+				start = byte_index;
+				SplitState::File
+			},
+			SplitState::Separator if is_rank_character(ch) => {
+				start = byte_index;
+				SplitState::Square
+			},
+			SplitState::File if is_file_character(ch) => SplitState::File,
+			SplitState::File | SplitState::Rank if is_rank_character(ch) => SplitState::Rank,
+			SplitState::Square if is_rank_character(ch) => SplitState::Square,
+			SplitState::Rank if is_file_character(ch) => {
+				positions.push(&token[start..byte_index]);
+				start = byte_index;
+				SplitState::File
+			},
+			_ => return Err(invalid_character(char_index))
+		};
+	}
+
+	// the token may not end with a separator
+	match state {
+		SplitState::Separator => return Err(invalid_character(last_index)),
+		_ => positions.push(&token[start..])
+	}
+
+	Ok((positions, markers))
+}
+
+///
+/// Parse a single board position from a string, e.g. `c3`
+///
+pub fn parse_position(position : &str) -> Result<BoardPosition, TokenError> {
+	token_validator(position.trim())
 }
 
 //
@@ -65,8 +282,9 @@ fn token_validator(token : &str) -> Result<BoardPosition, TokenError> {
 		return Err(TokenError::MissingRank { token : token.to_string() });
 	}
 
-	let row : usize = rank.parse::<usize>().unwrap();
-	let col : usize = file_to_row_position(&file);
+	let too_large = || TokenError::PositionTooLarge { token : token.to_string() };
+	let row : usize = try!(rank.parse::<usize>().ok().ok_or_else(&too_large));
+	let col : usize = try!(file_to_row_position(&file).ok_or_else(&too_large));
 
 	if row == 0 {
 		return Err(TokenError::ZeroRank { token : token.to_string() });
@@ -75,6 +293,16 @@ fn token_validator(token : &str) -> Result<BoardPosition, TokenError> {
 	Ok(BoardPosition::new(row - 1, col - 1))
 }
 
+fn numbered_square
+(square : &str, variant : Variant, size : usize)
+-> Result<BoardPosition, TokenError> {
+	square.parse::<usize>().ok()
+		.and_then(|number| square_to_position(variant, size, number))
+		.ok_or(TokenError::SquareOutOfRange {
+			token : square.to_string(),
+			max_square : number_of_squares(variant, size) })
+}
+
 enum ParseState {
 	File,
 	Rank
@@ -96,10 +324,10 @@ fn parse_file_rank(token : &str) -> Result<(String, String), TokenError> {
 		let (index, ch) = char_opt.unwrap();
 		match parse_state {
 			ParseState::File => {
-				if ch.is_alphabetic() {
+				if is_file_character(ch) {
 					file.push(ch);
 					char_opt = iter.next();
-				} else if ch.is_numeric() {
+				} else if is_rank_character(ch) {
 					parse_state = ParseState::Rank;
 				} else {
 					return Err(TokenError::InvalidCharacter {
@@ -107,7 +335,7 @@ fn parse_file_rank(token : &str) -> Result<(String, String), TokenError> {
 				}
 			}
 			ParseState::Rank => {
-				if ch.is_numeric() {
+				if is_rank_character(ch) {
 					rank.push(ch);
 					char_opt = iter.next();
 				} else {
@@ -121,18 +349,39 @@ fn parse_file_rank(token : &str) -> Result<(String, String), TokenError> {
 	Ok((file, rank))
 }
 
+// only the letters of the English alphabet name files
+fn is_file_character(ch : char) -> bool {
+	match ch {
+		'A'...'Z' | 'a'...'z' => true,
+		_ => false
+	}
+}
+
+// only decimal digits name ranks
+fn is_rank_character(ch : char) -> bool {
+	match ch {
+		'0'...'9' => true,
+		_ => false
+	}
+}
+
 //
-// Convert string of alphabetic characters to an index
+// Convert string of alphabetic characters to an index,
+// or None if the index is too large to be counted
 //
-fn file_to_row_position(file : &str) -> usize {
+fn file_to_row_position(file : &str) -> Option<usize> {
 	let mut row : usize = 0;
 	let alphabet_length = 26;
 
 	for c in file.chars() {
-		row = row * alphabet_length + char_to_position(c);
+		row = match row.checked_mul(alphabet_length)
+				.and_then(|row| row.checked_add(char_to_position(c))) {
+			Some(row) => row,
+			None => return None
+		};
 	}
 
-	row
+	Some(row)
 }
 
 //
@@ -155,11 +404,19 @@ mod test {
 
 use super::*;
 use checkers::BoardPosition;
+use checkers::Game;
+use checkers::Move;
+use checkers::read_fen;
+
+// reads a move of at least two positions on the board of American checkers
+fn parse_move(the_move : &str) -> Result<MoveInput, InputError> {
+	parse_positions(the_move, Variant::American, 8, 2)
+}
 
 fn test_parse_move(the_move : &str, exp_result : Vec<BoardPosition>) {
 	let result = parse_move(the_move).ok().unwrap();
 	
-	assert_eq!(exp_result, result);
+	assert_eq!(exp_result, result.positions);
 }
 
 ptest!(test_parse_move[
@@ -173,7 +430,9 @@ ptest!(test_parse_move[
 	test_parse_move_yy99_zz99("yy99 zz99", vec![BoardPosition::new(98, 674), BoardPosition::new(98, 701)]),
 	test_parse_move_aaa99_aaa99("aaa99 aaa99", vec![BoardPosition::new(98, 702), BoardPosition::new(98, 702)]),
 	test_parse_move_xfd13_ahh37("xfd13 ahh37", vec![BoardPosition::new(12, 16383), BoardPosition::new(36, 891)]),
-	test_parse_move_xx123_yy456_zz789("xx123 yy456 zz789", vec![BoardPosition::new(122, 647), BoardPosition::new(455, 674), BoardPosition::new(788, 701)])
+	test_parse_move_xx123_yy456_zz789("xx123 yy456 zz789", vec![BoardPosition::new(122, 647), BoardPosition::new(455, 674), BoardPosition::new(788, 701)]),
+	test_parse_move_l12_k11("l12 k11", vec![BoardPosition::new(11, 11), BoardPosition::new(10, 10)]),
+	test_parse_move_j10_l12("J10 L12", vec![BoardPosition::new(9, 9), BoardPosition::new(11, 11)])
 ]);
 
 fn test_parse_move_fail(the_move : &str, exp_result : InputError ) {
@@ -182,6 +441,165 @@ fn test_parse_move_fail(the_move : &str, exp_result : InputError ) {
 	assert_eq!(exp_result, result);
 }
 
+fn test_parse_numeric_move(the_move : &str, exp_result : Vec<BoardPosition>) {
+	let result = parse_move(the_move).ok().unwrap();
+	
+	assert_eq!(exp_result, result.positions);
+}
+
+ptest!(test_parse_numeric_move[
+	test_parse_numeric_move_11_15("11-15", vec![BoardPosition::new(5, 5), BoardPosition::new(4, 4)]),
+	test_parse_numeric_move_22x15("22x15", vec![BoardPosition::new(2, 2), BoardPosition::new(4, 4)]),
+	test_parse_numeric_move_27x18x11("27x18x11", vec![BoardPosition::new(1, 5), BoardPosition::new(3, 3), BoardPosition::new(5, 5)]),
+	test_parse_numeric_move_spaces("9 14", vec![BoardPosition::new(5, 1), BoardPosition::new(4, 2)]),
+	test_parse_numeric_move_mixed("c3 18", vec![BoardPosition::new(2, 2), BoardPosition::new(3, 3)]),
+	test_parse_numeric_move_capital_x("1X10", vec![BoardPosition::new(7, 1), BoardPosition::new(5, 3)])
+]);
+
+fn test_parse_numeric_move_fail(the_move : &str, exp_result : InputError) {
+	let result = parse_move(the_move).err().unwrap();
+	
+	assert_eq!(exp_result, result);
+}
+
+ptest!(test_parse_numeric_move_fail[
+	test_parse_numeric_move_fail_one_square("11", InputError::TooFewTokens),
+
+	test_parse_numeric_move_fail_zero("0-4",
+		InputError::InvalidTokens {
+			tokens : vec![
+				TokenError::SquareOutOfRange {
+					token : "0".to_string(),
+					max_square : 32 } ] }),
+
+	test_parse_numeric_move_fail_too_large("29-33",
+		InputError::InvalidTokens {
+			tokens : vec![
+				TokenError::SquareOutOfRange {
+					token : "33".to_string(),
+					max_square : 32 } ] }),
+
+	test_parse_numeric_move_fail_trailing_separator("11-",
+		InputError::InvalidTokens {
+			tokens : vec![
+				TokenError::InvalidCharacter {
+					token : "11-".to_string(),
+					char_index : 2 } ] }),
+
+	test_parse_numeric_move_fail_invalid_character("11+15",
+		InputError::InvalidTokens {
+			tokens : vec![
+				TokenError::InvalidCharacter {
+					token : "11+15".to_string(),
+					char_index : 2 } ] })
+]);
+
+#[test]
+fn test_parse_position() {
+	assert_eq!(Ok(BoardPosition::new(2, 2)), parse_position("c3"));
+	assert_eq!(Err(TokenError::ZeroRank { token : "c0".to_string() }), parse_position("c0"));
+	assert_eq!(Err(TokenError::MissingFile { token : "3".to_string() }), parse_position("3"));
+	assert_eq!(
+		Err(TokenError::PositionTooLarge { token : "zzzzzzzzzzzzzzzzzz1".to_string() }),
+		parse_position("zzzzzzzzzzzzzzzzzz1"));
+}
+
+ptest!(test_parse_move[
+	test_parse_move_a3_dash_b4("a3-b4", vec![BoardPosition::new(2, 0), BoardPosition::new(3, 1)]),
+	test_parse_move_a3b4("a3b4", vec![BoardPosition::new(2, 0), BoardPosition::new(3, 1)]),
+	test_parse_move_a5xc3xe1("a5xc3xe1", vec![BoardPosition::new(4, 0), BoardPosition::new(2, 2), BoardPosition::new(0, 4)]),
+	test_parse_move_capital_x("A5XC3", vec![BoardPosition::new(4, 0), BoardPosition::new(2, 2)]),
+	test_parse_move_x_file("a5xx3", vec![BoardPosition::new(4, 0), BoardPosition::new(2, 23)])
+]);
+
+fn test_capture_marker(the_move : &str, exp_result : CaptureMarker) {
+	let result = parse_move(the_move).ok().unwrap();
+	
+	assert_eq!(exp_result, result.marker);
+}
+
+ptest!(test_capture_marker[
+	test_capture_marker_unmarked("a3 b4", CaptureMarker::Unmarked),
+	test_capture_marker_no_capture("a3-b4", CaptureMarker::NoCapture),
+	test_capture_marker_capture("a5xc3 e1", CaptureMarker::Capture),
+	test_capture_marker_numeric("22x15", CaptureMarker::Capture)
+]);
+
+#[test]
+fn test_check_capture_marker() {
+	let simple_move = Move::new(vec![BoardPosition::new(2, 0), BoardPosition::new(3, 1)], vec![]);
+	let jump_move = Move::new(
+		vec![BoardPosition::new(4, 0), BoardPosition::new(2, 2)], vec![BoardPosition::new(3, 1)]);
+	
+	let input = parse_move("a3xb4").unwrap();
+	assert_eq!(Err(InputError::NotACapture), input.check_capture_marker(&simple_move));
+	
+	let input = parse_move("a5-c3").unwrap();
+	assert_eq!(Err(InputError::UnmarkedCapture), input.check_capture_marker(&jump_move));
+	
+	let input = parse_move("a5 c3").unwrap();
+	assert_eq!(Ok(()), input.check_capture_marker(&jump_move));
+}
+
+#[test]
+fn test_resolve_destination() {
+	let game = Game::new();
+	
+	let input = resolve_move("h4", &game).unwrap();
+	assert_eq!(vec![BoardPosition::new(2, 6), BoardPosition::new(3, 7)], input.positions);
+	
+	let input = resolve_move("c3 b4", &game).unwrap();
+	assert_eq!(vec![BoardPosition::new(2, 2), BoardPosition::new(3, 1)], input.positions);
+	
+	assert_eq!(
+		Err(InputError::NoMatchingMove { position : BoardPosition::new(4, 0) }),
+		resolve_move("a5", &game));
+	assert_eq!(Err(InputError::TooFewTokens), resolve_move("", &game));
+}
+
+#[test]
+fn test_resolve_ambiguous_destination() {
+	let game = Game::new();
+	
+	let candidates = vec![
+		Move::new(vec![BoardPosition::new(2, 0), BoardPosition::new(3, 1)], vec![]),
+		Move::new(vec![BoardPosition::new(2, 2), BoardPosition::new(3, 1)], vec![])];
+	match resolve_move("b4", &game) {
+		Err(InputError::AmbiguousMove { candidates : mut found }) => {
+			found.sort_by_key(|the_move| the_move.start().column);
+			assert_eq!(candidates, found);
+		},
+		result => panic!("unexpected result {:?}", result)
+	}
+}
+
+#[test]
+fn test_resolve_jumps() {
+	let game = read_fen(Variant::American, "W:W22:B18").unwrap();
+	let input = resolve_move("c3", &game).unwrap();
+	assert_eq!(vec![BoardPosition::new(2, 2), BoardPosition::new(4, 4)], input.positions);
+	
+	// the jumps a multiple jump passes through are given in full
+	let game = read_fen(Variant::American, "W:W22:B18,11,1").unwrap();
+	let input = resolve_move("c3", &game).unwrap();
+	assert_eq!(
+		vec![BoardPosition::new(2, 2), BoardPosition::new(4, 4), BoardPosition::new(6, 6)],
+		input.positions);
+	let input = resolve_move("c3 e5", &game).unwrap();
+	assert_eq!(vec![BoardPosition::new(2, 2), BoardPosition::new(4, 4)], input.positions);
+	
+	let game = read_fen(Variant::American, "W:W27:B23,15").unwrap();
+	let input = resolve_move("f2xf6", &game).unwrap();
+	assert_eq!(CaptureMarker::Capture, input.marker);
+	assert_eq!(
+		vec![BoardPosition::new(1, 5), BoardPosition::new(3, 3), BoardPosition::new(5, 5)],
+		input.positions);
+	
+	// moves that match nothing are left for the game to reject
+	let input = resolve_move("f2 h4", &game).unwrap();
+	assert_eq!(vec![BoardPosition::new(1, 5), BoardPosition::new(3, 7)], input.positions);
+}
+
 ptest!(test_parse_move_fail[
 	test_parse_move_fail_too_few_tokens_empty("", InputError::TooFewTokens),
 	test_parse_move_fail_too_few_tokens_a1("a1", InputError::TooFewTokens),
@@ -192,18 +610,14 @@ ptest!(test_parse_move_fail[
 			tokens : vec![TokenError::MissingRank {
 				token : "a".to_string() } ] }),
 
-	test_parse_move_fail_missing_file_a1_1("a1 1",
-		InputError::InvalidTokens {
-			tokens : vec![TokenError::MissingFile {
-				token : "1".to_string() } ] }),
-
-	test_parse_move_fail_missing_rank_file_a_1("a 1",
+	test_parse_move_fail_missing_rank_a_33("a 33",
 		InputError::InvalidTokens {
 			tokens : vec![
 				TokenError::MissingRank {
 					token : "a".to_string() },
-				TokenError::MissingFile {
-					token : "1".to_string() } ] }),
+				TokenError::SquareOutOfRange {
+					token : "33".to_string(),
+					max_square : 32 } ] }),
 
 	test_parse_move_fail_zero_rank("a1 a0", 
 		InputError::InvalidTokens {
@@ -218,6 +632,20 @@ ptest!(test_parse_move_fail[
 					token : "a$".to_string(),
 					char_index : 1 } ] }),
 
+	test_parse_move_fail_non_ascii_file("a1 é2",
+		InputError::InvalidTokens {
+			tokens : vec![
+				TokenError::InvalidCharacter {
+					token : "é2".to_string(),
+					char_index : 0 } ] }),
+
+	test_parse_move_fail_non_ascii_rank("a1 b٣",
+		InputError::InvalidTokens {
+			tokens : vec![
+				TokenError::InvalidCharacter {
+					token : "b٣".to_string(),
+					char_index : 1 } ] }),
+
 	test_parse_move_fail_invalid_characters("#1 a$",
 		InputError::InvalidTokens {
 			tokens : vec![
@@ -226,6 +654,47 @@ ptest!(test_parse_move_fail[
 					char_index : 0 },
 				TokenError::InvalidCharacter {
 					token : "a$".to_string(),
-					char_index : 1 } ] })
+					char_index : 1 } ] }),
+
+	test_parse_move_fail_mixed_markers("a5xc3-e1", InputError::MixedMarkers),
+
+	test_parse_move_fail_trailing_separator("a3-",
+		InputError::InvalidTokens {
+			tokens : vec![
+				TokenError::InvalidCharacter {
+					token : "a3-".to_string(),
+					char_index : 2 } ] }),
+
+	test_parse_move_fail_leading_separator("a3 -b4",
+		InputError::InvalidTokens {
+			tokens : vec![
+				TokenError::InvalidCharacter {
+					token : "-b4".to_string(),
+					char_index : 0 } ] }),
+
+	test_parse_move_fail_rank_before_file("3a 4b",
+		InputError::InvalidTokens {
+			tokens : vec![
+				TokenError::InvalidCharacter {
+					token : "3a".to_string(),
+					char_index : 1 },
+				TokenError::InvalidCharacter {
+					token : "4b".to_string(),
+					char_index : 1 } ] }),
+
+	test_parse_move_fail_missing_rank_b("a3b",
+		InputError::InvalidTokens {
+			tokens : vec![TokenError::MissingRank {
+				token : "b".to_string() } ] }),
+
+	test_parse_move_fail_rank_too_large("a99999999999999999999 b4",
+		InputError::InvalidTokens {
+			tokens : vec![TokenError::PositionTooLarge {
+				token : "a99999999999999999999".to_string() } ] }),
+
+	test_parse_move_fail_file_too_large("zzzzzzzzzzzzzzzzzz1 b4",
+		InputError::InvalidTokens {
+			tokens : vec![TokenError::PositionTooLarge {
+				token : "zzzzzzzzzzzzzzzzzz1".to_string() } ] })
 ]);
 }
@@ -1,24 +1,80 @@
 mod ai;
 pub use checkers::ai::{
+	CaptureRules,
 	Direction,
-	find_simple_moves_for_king,
-	find_jump_moves_for_king,
-	find_simple_moves_for_man,
-	find_jump_moves_for_man,
+	find_captured_positions,
+	find_simple_moves,
+	find_jump_moves,
 	JumpMove,
+	Move,
+	Movement,
+	MoveVector,
 	SimpleMove};
 
 mod board;
-pub use checkers::board::{Board, BoardPosition};
+pub use checkers::board::{Board, BoardPosition, Orientation};
+
+mod clock;
+pub use checkers::clock::{Clock, TimeBonus, TimeControl};
+
+mod command;
+pub use checkers::command::{
+	Command,
+	CommandError,
+	CommandInfo,
+	COMMANDS,
+	parse_command,
+	suggest_commands};
 
 mod display;
-pub use checkers::display::print_board;
+pub use checkers::display::{
+	column_to_file,
+	ColorScheme,
+	format_clock_time,
+	format_move,
+	format_position,
+	Notation,
+	PlayerColor,
+	print_board_facing};
+
+mod fen;
+pub use checkers::fen::{FenError, read_fen, write_fen};
 
 mod game;
-pub use checkers::game::{Game, GameState, MoveError};
+pub use checkers::game::{
+	ActionError,
+	DrawReason,
+	DrawRules,
+	Game,
+	GameSnapshot,
+	GameState,
+	MAX_BOARD_SIZE,
+	MoveError,
+	PositionBuilder,
+	SetupError,
+	SnapshotError,
+	WinReason};
+
+mod handicap;
+pub use checkers::handicap::Handicap;
 
 mod input;
-pub use checkers::input::{InputError, parse_move, TokenError};
+pub use checkers::input::{
+	CaptureMarker,
+	InputError,
+	MoveInput,
+	parse_position,
+	resolve_move,
+	TokenError};
+
+mod notation;
+pub use checkers::notation::{number_of_squares, position_to_square, square_to_position};
+
+mod observer;
+pub use checkers::observer::{GameEvent, GameObserver};
+
+mod pdn;
+pub use checkers::pdn::{PdnError, PdnGame, PdnMove, read_pdn};
 
 mod piece;
 pub use checkers::piece::{KingPiece, ManPiece, Piece, PieceType};
@@ -26,5 +82,14 @@ pub use checkers::piece::{KingPiece, ManPiece, Piece, PieceType};
 mod player;
 pub use checkers::player::Player;
 
+mod save;
+pub use checkers::save::{read_save, SaveError, SAVE_FORMAT_VERSION, write_save};
+
+mod statistics;
+pub use checkers::statistics::{GameStatistics, PlayerStatistics};
+
 mod tile;
 pub use checkers::tile::{EmptyTile, OccupiedTile, Tile};
+
+mod variant;
+pub use checkers::variant::{CapturePriority, Variant, WinCondition};
@@ -0,0 +1,102 @@
+use checkers::board::BoardPosition;
+use checkers::variant::Variant;
+
+/// The number of the tile at `position` in numeric notation, where the
+/// playable tiles of a `size` by `size` board of `variant` are numbered
+/// from 1, row by row from the highest rank and from left to right in
+/// each row. On an American checkerboard `b8` is square 1 and `g1` is
+/// square 32. Returns `None` if the tile is not playable or not on the
+/// board.
+pub fn position_to_square
+(variant : Variant, size : usize, position : BoardPosition)
+-> Option<usize> {
+	if position.row >= size || position.column >= size
+			|| !variant.is_playable_tile(position.row, position.column) {
+		return None;
+	}
+
+	let mut square = 0;
+	for r in (position.row..size).rev() {
+		for c in 0..size {
+			if variant.is_playable_tile(r, c) {
+				square += 1;
+			}
+
+			if r == position.row && c == position.column {
+				return Some(square);
+			}
+		}
+	}
+
+	unreachable!()
+}
+
+/// The position of the tile numbered `square` in numeric notation, as
+/// described by `position_to_square`. Returns `None` if the board has
+/// no such square.
+pub fn square_to_position
+(variant : Variant, size : usize, square : usize)
+-> Option<BoardPosition> {
+	let mut number = 0;
+	for r in (0..size).rev() {
+		for c in 0..size {
+			if variant.is_playable_tile(r, c) {
+				number += 1;
+				if number == square {
+					return Some(BoardPosition::new(r, c));
+				}
+			}
+		}
+	}
+
+	None
+}
+
+/// The number of squares of a `size` by `size` board of
+/// `variant` in numeric notation, e.g. 32 in American checkers
+pub fn number_of_squares(variant : Variant, size : usize) -> usize {
+	(0..size)
+		.map(|r| (0..size).filter(|&c| variant.is_playable_tile(r, c)).count())
+		.sum()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use checkers::board::BoardPosition;
+	use checkers::variant::Variant;
+
+	fn test_american_square(square : usize, row : usize, column : usize) {
+		let position = BoardPosition::new(row, column);
+		assert_eq!(Some(position), square_to_position(Variant::American, 8, square));
+		assert_eq!(Some(square), position_to_square(Variant::American, 8, position));
+	}
+
+	ptest!(test_american_square [
+		test_american_square_1(1, 7, 1),
+		test_american_square_4(4, 7, 7),
+		test_american_square_5(5, 6, 0),
+		test_american_square_11(11, 5, 5),
+		test_american_square_15(15, 4, 4),
+		test_american_square_29(29, 0, 0),
+		test_american_square_32(32, 0, 6)
+	]);
+
+	#[test]
+	fn squares_outside_the_board() {
+		assert_eq!(None, square_to_position(Variant::American, 8, 0));
+		assert_eq!(None, square_to_position(Variant::American, 8, 33));
+		assert_eq!(None, position_to_square(Variant::American, 8, BoardPosition::new(0, 1)));
+		assert_eq!(None, position_to_square(Variant::American, 8, BoardPosition::new(8, 0)));
+	}
+
+	#[test]
+	fn squares_of_other_boards() {
+		assert_eq!(Some(BoardPosition::new(7, 0)), square_to_position(Variant::Italian, 8, 1));
+		assert_eq!(Some(BoardPosition::new(11, 1)), square_to_position(Variant::Canadian, 12, 1));
+		assert_eq!(Some(72), position_to_square(Variant::Canadian, 12, BoardPosition::new(0, 10)));
+		assert_eq!(72, number_of_squares(Variant::Canadian, 12));
+		assert_eq!(64, number_of_squares(Variant::Turkish, 8));
+	}
+}
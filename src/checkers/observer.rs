@@ -0,0 +1,39 @@
+use checkers::ai::Move;
+use checkers::board::BoardPosition;
+use checkers::game::GameState;
+
+/// Something that happened in a `Game`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GameEvent {
+	/// The player with ID `player_id` made `the_move`
+	MoveMade{ player_id : u32, the_move : Move },
+
+	/// The player with ID `player_id` captured the pieces at `positions`
+	PiecesCaptured{ player_id : u32, positions : Vec<BoardPosition> },
+
+	/// A man of the player with ID `player_id` was crowned at `position`
+	PieceCrowned{ player_id : u32, position : BoardPosition },
+
+	/// The move `the_move` made by the player with
+	/// ID `player_id` was taken back
+	MoveUndone{ player_id : u32, the_move : Move },
+
+	/// It is now the turn of the player with ID `player_id`
+	TurnChanged{ player_id : u32 },
+
+	/// The game finished in `state`
+	GameOver{ state : GameState }
+}
+
+/// Receives the events of a game as they happen, e.g. to log the
+/// game, update a user interface or broadcast the moves. Any closure
+/// taking a `&GameEvent` can be used as an observer.
+pub trait GameObserver {
+	fn on_event(&mut self, event : &GameEvent);
+}
+
+impl<F> GameObserver for F where F : FnMut(&GameEvent) {
+	fn on_event(&mut self, event : &GameEvent) {
+		self(event)
+	}
+}
@@ -0,0 +1,762 @@
+extern crate std;
+
+use std::time::Duration;
+
+use checkers::ai::Move;
+use checkers::board::BoardPosition;
+use checkers::fen::{color_to_player_id, format_fen, player_id_to_color,
+	read_fen_position, FenError};
+use checkers::game::{Game, GameState, PositionBuilder};
+use checkers::handicap::Handicap;
+use checkers::notation::{position_to_square, square_to_position};
+use checkers::variant::Variant;
+
+/// The reasons a game cannot be read from or written to PDN
+#[derive(Debug, PartialEq, Eq)]
+pub enum PdnError {
+	/// A tag pair is not of the form `[Name "value"]`
+	MalformedTag{ tag : String },
+
+	/// A comment or a variation is not closed
+	Unterminated,
+
+	/// A token of the move text is neither a move,
+	/// a move number nor a result
+	InvalidToken{ token : String },
+
+	/// The `GameType` tag names a variant that is not supported
+	UnsupportedGameType{ game_type : String },
+
+	/// The variant, or the size of its board, cannot be written in PDN
+	UnsupportedVariant{ variant : Variant },
+
+	/// The `FEN` tag does not hold a valid position
+	Fen(FenError),
+
+	/// The `Handicap` tag is not of the form `B:R1,3:K30:T60`
+	InvalidHandicap{ handicap : String },
+
+	/// The move at `index` in the list of moves is not legal in the
+	/// position reached by the moves before it
+	IllegalMove{ index : usize, the_move : String },
+
+	/// The result of the game does not match the final position
+	ResultMismatch{ result : String }
+}
+
+/// A move written in numeric notation, e.g. `11-15` or `27x18x11`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PdnMove {
+	/// The squares the piece moves through, from its starting square
+	pub squares : Vec<usize>,
+
+	/// Whether the move is written as a capture with `x`
+	pub capture : bool,
+
+	/// The comment following the move, if any
+	pub comment : Option<String>
+}
+
+impl PdnMove {
+	fn new(squares : Vec<usize>, capture : bool) -> PdnMove {
+		PdnMove{ squares : squares, capture : capture, comment : None }
+	}
+
+	/// The move as it is written in the move text, e.g. `22x15`
+	pub fn text(&self) -> String {
+		let separator = if self.capture { "x" } else { "-" };
+		self.squares.iter()
+			.map(|square| square.to_string())
+			.collect::<Vec<_>>()
+			.join(separator)
+	}
+
+	// reads a move such as `11-15` or `27x18x11`, allowing
+	// trailing annotations such as `!` or `?`
+	fn parse(token : &str) -> Option<PdnMove> {
+		let token = token.trim_right_matches(|ch : char| ch == '!' || ch == '?');
+		let capture = token.contains('x') || token.contains('X');
+		let separators : &[char] = if capture { &['x', 'X'] } else { &['-'] };
+
+		let squares : Option<Vec<_>> = token.split(separators)
+			.map(|square| square.parse::<usize>().ok())
+			.collect();
+		match squares {
+			Some(ref squares) if squares.len() >= 2 =>
+				Some(PdnMove::new(squares.clone(), capture)),
+			_ => None
+		}
+	}
+}
+
+/// A game in Portable Draughts Notation, the standard format for
+/// exchanging checkers games. A game consists of tag pairs such as
+/// `[Event "Club championship"]`, followed by the moves in numeric
+/// notation and the result.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PdnGame {
+	/// The tag pairs in the order they are written
+	pub tags : Vec<(String, String)>,
+
+	/// The comment before the first move, if any
+	pub comment : Option<String>,
+
+	pub moves : Vec<PdnMove>,
+
+	/// The result token ending the move text: `1-0` when White wins,
+	/// `0-1` when Black wins, `1/2-1/2` for a draw and `*` when
+	/// the game is unfinished
+	pub result : String
+}
+
+const UNFINISHED : &'static str = "*";
+
+// the results as written by PDN for English draughts, followed by the
+// results with two points for a win, as written for other variants
+const WHITE_WINS : [&'static str; 2] = ["1-0", "2-0"];
+const BLACK_WINS : [&'static str; 2] = ["0-1", "0-2"];
+const DRAWN : [&'static str; 2] = ["1/2-1/2", "1-1"];
+
+fn is_result(token : &str) -> bool {
+	token == UNFINISHED
+		|| WHITE_WINS.contains(&token)
+		|| BLACK_WINS.contains(&token)
+		|| DRAWN.contains(&token)
+}
+
+// the ID of the player who moves first in the starting position of a
+// variant; in American checkers Black, who plays down the board, starts
+fn standard_first_player_id(variant : Variant) -> u32 {
+	match variant {
+		Variant::American | Variant::Giveaway => 2,
+		Variant::Italian | Variant::Turkish | Variant::Canadian => 1
+	}
+}
+
+// writes `handicap` as the color of the player giving the odds,
+// followed by the squares of the removed pieces after `R`, the
+// squares of the extra kings after `K` and the extra seconds after
+// `T`, e.g. `B:R1,3:K30:T60`, leaving out the odds not given
+fn format_handicap(variant : Variant, size : usize, handicap : &Handicap) -> String {
+	let squares = |positions : &[BoardPosition]| positions.iter()
+		.map(|position| position_to_square(variant, size, *position).unwrap().to_string())
+		.collect::<Vec<_>>()
+		.join(",");
+
+	let mut fields = vec![player_id_to_color(handicap.player_id).to_string()];
+	if !handicap.removed.is_empty() {
+		fields.push(format!("R{}", squares(&handicap.removed)));
+	}
+	if !handicap.extra_kings.is_empty() {
+		fields.push(format!("K{}", squares(&handicap.extra_kings)));
+	}
+	if handicap.extra_time > Duration::from_secs(0) {
+		fields.push(format!("T{}", handicap.extra_time.as_secs()));
+	}
+
+	fields.join(":")
+}
+
+// reads a handicap as written by `format_handicap`
+fn parse_handicap(variant : Variant, size : usize, handicap : &str) -> Option<Handicap> {
+	let mut fields = handicap.trim().split(':');
+	let player_id = match fields.next().and_then(|color| color_to_player_id(color.trim())) {
+		Some(player_id) => player_id,
+		None => return None
+	};
+
+	let positions = |squares : &str| -> Option<Vec<_>> {
+		squares.split(',')
+			.map(|square| square.trim().parse::<usize>().ok()
+				.and_then(|square| square_to_position(variant, size, square)))
+			.collect()
+	};
+
+	let mut handicap = Handicap::new(player_id);
+	for field in fields {
+		let field = field.trim();
+		let odds = field.get(1..).unwrap_or("");
+		handicap = match field.get(0..1) {
+			Some("R") => match positions(odds) {
+				Some(removed) => removed.into_iter().fold(handicap, Handicap::remove),
+				None => return None
+			},
+			Some("K") => match positions(odds) {
+				Some(extra_kings) => extra_kings.into_iter().fold(handicap, Handicap::extra_king),
+				None => return None
+			},
+			Some("T") => match odds.parse::<u64>() {
+				Ok(seconds) => handicap.extra_time(Duration::from_secs(seconds)),
+				Err(_) => return None
+			},
+			_ => return None
+		};
+	}
+
+	Some(handicap)
+}
+
+impl PdnGame {
+	fn new() -> PdnGame {
+		PdnGame{
+			tags : Vec::new(),
+			comment : None,
+			moves : Vec::new(),
+			result : UNFINISHED.to_string()}
+	}
+
+	/// Records `game` from its starting position. The `Event`, `Date`,
+	/// `White` and `Black` tags are unknown until they are set with
+	/// `set_tag`. A `FEN` tag is added when the game did not start from
+	/// the standard starting position of its variant, e.g. when it was
+	/// played with a handicap, which is written in a `Handicap` tag.
+	pub fn from_game(game : &Game) -> Result<PdnGame, PdnError> {
+		let variant = game.variant();
+		let size = game.board().number_rows();
+		let game_type = match variant.pdn_game_type() {
+			Some(game_type) if size == variant.board_size() => game_type,
+			_ => return Err(PdnError::UnsupportedVariant{variant : variant})
+		};
+
+		let result = match *game.state() {
+			GameState::InProgress => UNFINISHED,
+			GameState::GameOver{winner_id : 1, ..} => WHITE_WINS[0],
+			GameState::GameOver{..} => BLACK_WINS[0],
+			GameState::Draw{..} => DRAWN[0]
+		};
+
+		let mut pdn_game = PdnGame::new();
+		pdn_game.set_tag("Event", "?");
+		pdn_game.set_tag("Date", "????.??.??");
+		pdn_game.set_tag("White", "?");
+		pdn_game.set_tag("Black", "?");
+		pdn_game.set_tag("Result", result);
+		pdn_game.set_tag("GameType", &game_type.to_string());
+
+		let standard_game = Game::with_variant(variant);
+		let standard_fen = format_fen(variant, size,
+			standard_first_player_id(variant), &standard_game.starting_pieces());
+		let starting_fen = format_fen(variant, size,
+			game.first_player_id(), &game.starting_pieces());
+		if starting_fen != standard_fen || game.handicap().is_some() {
+			pdn_game.set_tag("FEN", &starting_fen);
+		}
+
+		if let Some(handicap) = game.handicap() {
+			pdn_game.set_tag("Handicap", &format_handicap(variant, size, handicap));
+		}
+
+		pdn_game.moves = game.moves().iter()
+			.map(|the_move| {
+				let squares = the_move.path().iter()
+					.map(|position| position_to_square(variant, size, *position).unwrap())
+					.collect();
+				PdnMove::new(squares, the_move.is_jump())
+			})
+			.collect();
+		pdn_game.result = result.to_string();
+
+		Ok(pdn_game)
+	}
+
+	/// The value of the tag called `name`, if the game has one
+	pub fn tag(&self, name : &str) -> Option<&str> {
+		self.tags.iter()
+			.find(|&&(ref tag_name, _)| tag_name == name)
+			.map(|&(_, ref value)| &**value)
+	}
+
+	/// Sets the value of the tag called `name`, adding the
+	/// tag after the other tags if the game does not have it
+	pub fn set_tag(&mut self, name : &str, value : &str) {
+		if let Some(tag) = self.tags.iter_mut().find(|&&mut (ref tag_name, _)| tag_name == name) {
+			tag.1 = value.to_string();
+			return;
+		}
+
+		self.tags.push((name.to_string(), value.to_string()));
+	}
+
+	/// The variant named by the `GameType` tag. Games without
+	/// the tag are American checkers.
+	pub fn variant(&self) -> Result<Variant, PdnError> {
+		let game_type = match self.tag("GameType") {
+			Some(game_type) => game_type,
+			None => return Ok(Variant::American)
+		};
+
+		// the game type may be followed by a description of the board,
+		// e.g. `21,B,8,8,N1,0`, which is implied by the variant
+		game_type.split(',').next()
+			.and_then(|number| number.trim().parse::<u32>().ok())
+			.and_then(Variant::from_pdn_game_type)
+			.ok_or(PdnError::UnsupportedGameType{game_type : game_type.to_string()})
+	}
+
+	/// Replays the moves from the starting position, which validates
+	/// every move, and returns the game in its final position. The
+	/// starting position of a game with a `Handicap` tag already
+	/// includes the odds, which are kept with the game. A game whose
+	/// result is not shown by its final position ends after its last
+	/// move, by resignation of the losing player or by agreement.
+	pub fn replay(&self) -> Result<Game, PdnError> {
+		let variant = try!(self.variant());
+		let mut builder = match self.tag("FEN") {
+			Some(fen) => try!(read_fen_position(variant, fen).map_err(PdnError::Fen)),
+			None => PositionBuilder::new(variant)
+				.pieces(&Game::with_variant(variant).starting_pieces())
+				.side_to_move(standard_first_player_id(variant))
+		};
+
+		if let Some(handicap) = self.tag("Handicap") {
+			let handicap = try!(parse_handicap(variant, variant.board_size(), handicap)
+				.ok_or(PdnError::InvalidHandicap{handicap : handicap.to_string()}));
+			builder = builder.handicap(handicap);
+		}
+
+		let mut game = try!(builder.build()
+			.map_err(|error| PdnError::Fen(FenError::Setup(error))));
+
+		for (index, pdn_move) in self.moves.iter().enumerate() {
+			let illegal_move = || PdnError::IllegalMove{
+				index : index, the_move : pdn_move.text()};
+
+			let the_move = try!(self.find_move(&game, pdn_move).ok_or_else(&illegal_move));
+			try!(game.apply(the_move).map_err(|_| illegal_move()));
+		}
+
+		let result = &*self.result;
+
+		// the players of a game in progress cannot refuse to end it
+		if *game.state() == GameState::InProgress {
+			if WHITE_WINS.contains(&result) {
+				game.resign(2).unwrap();
+			} else if BLACK_WINS.contains(&result) {
+				game.resign(1).unwrap();
+			} else if DRAWN.contains(&result) {
+				game.offer_draw(1).unwrap();
+				game.accept_draw(2).unwrap();
+			}
+		}
+
+		let consistent = match *game.state() {
+			GameState::InProgress => true,
+			GameState::GameOver{winner_id, ..} => result == UNFINISHED
+				|| (winner_id == 1 && WHITE_WINS.contains(&result))
+				|| (winner_id == 2 && BLACK_WINS.contains(&result)),
+			GameState::Draw{..} => result == UNFINISHED || DRAWN.contains(&result)
+		};
+
+		if !consistent {
+			return Err(PdnError::ResultMismatch{result : self.result.clone()});
+		}
+
+		Ok(game)
+	}
+
+	// finds the legal move written as `pdn_move`. Captures may be written
+	// with only their starting and final squares, e.g. `27x11` for
+	// `27x18x11`, as long as a single capture matches.
+	fn find_move(&self, game : &Game, pdn_move : &PdnMove) -> Option<Move> {
+		let variant = game.variant();
+		let size = game.board().number_rows();
+		let path : Option<Vec<_>> = pdn_move.squares.iter()
+			.map(|square| square_to_position(variant, size, *square))
+			.collect();
+		let path = match path {
+			Some(path) => path,
+			None => return None
+		};
+
+		if let Ok(the_move) = game.find_move(&path) {
+			return if the_move.is_jump() == pdn_move.capture { Some(the_move) } else { None };
+		}
+
+		if !pdn_move.capture || path.len() != 2 {
+			return None;
+		}
+
+		let mut candidates : Vec<_> = game.legal_moves().into_iter()
+			.filter(|the_move| the_move.is_jump()
+				&& the_move.start() == path[0]
+				&& the_move.end() == path[1])
+			.collect();
+		if candidates.len() == 1 { candidates.pop() } else { None }
+	}
+
+	/// Writes the game in PDN, with the tag pairs on separate lines
+	/// followed by the numbered moves and the result
+	pub fn to_pdn(&self) -> String {
+		let mut pdn = String::new();
+		for &(ref name, ref value) in &self.tags {
+			pdn.push_str(&format!("[{} \"{}\"]\n", name, value.replace('"', "\\\"")));
+		}
+		pdn.push('\n');
+
+		let mut tokens = Vec::new();
+		if let Some(ref comment) = self.comment {
+			tokens.push(format!("{{{}}}", comment));
+		}
+
+		for (index, pdn_move) in self.moves.iter().enumerate() {
+			if index % 2 == 0 {
+				tokens.push(format!("{}.", index / 2 + 1));
+			}
+
+			tokens.push(pdn_move.text());
+			if let Some(ref comment) = pdn_move.comment {
+				tokens.push(format!("{{{}}}", comment));
+			}
+		}
+		tokens.push(self.result.clone());
+
+		// wrap the move text to keep lines short
+		let mut line_length = 0;
+		for token in tokens {
+			if line_length > 0 && line_length + token.len() + 1 > 79 {
+				pdn.push('\n');
+				line_length = 0;
+			} else if line_length > 0 {
+				pdn.push(' ');
+				line_length += 1;
+			}
+
+			pdn.push_str(&token);
+			line_length += token.len();
+		}
+		pdn.push('\n');
+
+		pdn
+	}
+}
+
+// reads the text up to the character `end`, which is consumed
+fn read_until<I>(chars : &mut I, end : char) -> Result<String, PdnError>
+where I : Iterator<Item = char> {
+	let mut text = String::new();
+	for ch in chars {
+		if ch == end {
+			return Ok(text);
+		}
+		text.push(ch);
+	}
+
+	Err(PdnError::Unterminated)
+}
+
+// reads a tag pair such as `Event "Club championship"`
+fn parse_tag(tag : &str) -> Result<(String, String), PdnError> {
+	let malformed = || PdnError::MalformedTag{tag : tag.to_string()};
+	let tag = tag.trim();
+	let name_end = try!(tag.find(char::is_whitespace).ok_or_else(&malformed));
+	let (name, value) = tag.split_at(name_end);
+	let value = value.trim();
+	if value.len() < 2 || !value.starts_with('"') || !value.ends_with('"') {
+		return Err(malformed());
+	}
+
+	let value = value[1..value.len() - 1].replace("\\\"", "\"");
+	Ok((name.to_string(), value))
+}
+
+/// Reads every game in `pdn`. The games are not validated
+/// until they are replayed with `PdnGame::replay`.
+pub fn read_pdn(pdn : &str) -> Result<Vec<PdnGame>, PdnError> {
+	let mut games = Vec::new();
+	let mut game = PdnGame::new();
+	let mut in_move_text = false;
+	let mut chars = pdn.chars().peekable();
+
+	loop {
+		let ch = match chars.peek() {
+			Some(&ch) => ch,
+			None => break
+		};
+
+		if ch.is_whitespace() {
+			chars.next();
+			continue;
+		}
+
+		match ch {
+			'[' => {
+				chars.next();
+				let tag = try!(read_until(&mut chars, ']'));
+				// tags after the moves of a game without
+				// a result start the next game
+				if in_move_text {
+					games.push(game);
+					game = PdnGame::new();
+					in_move_text = false;
+				}
+
+				let (name, value) = try!(parse_tag(&tag));
+				game.set_tag(&name, &value);
+			},
+			'{' => {
+				chars.next();
+				let comment = try!(read_until(&mut chars, '}')).trim().to_string();
+				in_move_text = true;
+				match game.moves.last_mut() {
+					Some(last_move) => last_move.comment = Some(comment),
+					None => game.comment = Some(comment)
+				}
+			},
+			'(' => {
+				// variations are skipped
+				chars.next();
+				let mut depth = 1;
+				while depth > 0 {
+					match chars.next() {
+						Some('(') => depth += 1,
+						Some(')') => depth -= 1,
+						Some(_) => {},
+						None => return Err(PdnError::Unterminated)
+					}
+				}
+			},
+			_ => {
+				let mut token = String::new();
+				while let Some(&ch) = chars.peek() {
+					if ch.is_whitespace() || ch == '[' || ch == '{' || ch == '(' {
+						break;
+					}
+					token.push(ch);
+					chars.next();
+				}
+
+				in_move_text = true;
+				if is_result(&token) {
+					game.result = token;
+					games.push(game);
+					game = PdnGame::new();
+					in_move_text = false;
+					continue;
+				}
+
+				// move numbers such as `12.` or `12...` may be
+				// written without a space before the move
+				let the_move = match token.rfind('.') {
+					Some(index) if token[..index].trim_right_matches('.')
+						.chars().all(|ch| ch.is_digit(10)) => &token[index + 1..],
+					_ => &token[..]
+				};
+
+				if !the_move.is_empty() {
+					let pdn_move = try!(PdnMove::parse(the_move)
+						.ok_or(PdnError::InvalidToken{token : token.clone()}));
+					game.moves.push(pdn_move);
+				}
+			}
+		}
+	}
+
+	if in_move_text || !game.tags.is_empty() {
+		games.push(game);
+	}
+
+	Ok(games)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use std::time::Duration;
+
+	use checkers::board::BoardPosition;
+	use checkers::game::{Game, GameState, WinReason};
+	use checkers::handicap::Handicap;
+	use checkers::piece::PieceType;
+	use checkers::variant::Variant;
+
+	const SHORT_GAME : &'static str = "[Event \"Club \\\"open\\\" night\"]
+[Date \"2016.05.01\"]
+[White \"Ann\"]
+[Black \"Bob\"]
+[Result \"*\"]
+[GameType \"21\"]
+
+{Old Fourteenth} 1. 11-15 23-19 2. 8-11 22-17 {a quiet line}
+3. 4-8 17-13 4. 15-18 27-23 (4... 9-14) 5. 18x27 32x23 *
+";
+
+	#[test]
+	fn read_tags_moves_and_comments() {
+		let games = read_pdn(SHORT_GAME).unwrap();
+		assert_eq!(1, games.len());
+
+		let game = &games[0];
+		assert_eq!(Some("Club \"open\" night"), game.tag("Event"));
+		assert_eq!(Some("Bob"), game.tag("Black"));
+		assert_eq!(None, game.tag("FEN"));
+		assert_eq!(Some("Old Fourteenth".to_string()), game.comment);
+		assert_eq!("*", game.result);
+
+		assert_eq!(10, game.moves.len());
+		assert_eq!(PdnMove::new(vec![11, 15], false), game.moves[0]);
+		assert_eq!(Some("a quiet line".to_string()), game.moves[3].comment);
+		assert_eq!(PdnMove::new(vec![18, 27], true), game.moves[8]);
+	}
+
+	#[test]
+	fn replay_validates_moves() {
+		let games = read_pdn(SHORT_GAME).unwrap();
+		let game = games[0].replay().unwrap();
+		assert_eq!(GameState::InProgress, *game.state());
+		assert_eq!(10, game.moves().len());
+
+		// 32x23 leaves a white man on 23
+		let piece = game.board().get_tile(2, 4).get_piece().unwrap();
+		assert_eq!((1, PieceType::Man), (piece.get_player_id(), piece.get_type()));
+
+		let illegal = read_pdn("1. 11-15 23-18 2. 15-22 *").unwrap();
+		assert_eq!(
+			Err(PdnError::IllegalMove{index : 2, the_move : "15-22".to_string()}),
+			illegal[0].replay().map(|_| ()));
+	}
+
+	#[test]
+	fn capture_marker_must_match_move() {
+		let games = read_pdn("1. 11x15 *").unwrap();
+		assert_eq!(
+			Err(PdnError::IllegalMove{index : 0, the_move : "11x15".to_string()}),
+			games[0].replay().map(|_| ()));
+	}
+
+	#[test]
+	fn read_several_games_and_results() {
+		let pdn = "[Event \"one\"]\n1. 11-15 1-0\n[Event \"two\"]\n1. 9-13 21-17 1/2-1/2\n";
+		let games = read_pdn(pdn).unwrap();
+		assert_eq!(2, games.len());
+		assert_eq!("1-0", games[0].result);
+		assert_eq!(
+			GameState::GameOver{winner_id : 1, reason : WinReason::Resignation, move_number : 1},
+			*games[0].replay().unwrap().state());
+		assert_eq!(Some("two"), games[1].tag("Event"));
+		assert_eq!(2, games[1].moves.len());
+		assert_eq!("1/2-1/2", games[1].result);
+	}
+
+	#[test]
+	fn reject_bad_pdn() {
+		assert_eq!(Err(PdnError::Unterminated), read_pdn("1. 11-15 {comment"));
+		assert_eq!(
+			Err(PdnError::MalformedTag{tag : "Event".to_string()}),
+			read_pdn("[Event]"));
+		assert_eq!(
+			Err(PdnError::InvalidToken{token : "11:15".to_string()}),
+			read_pdn("1. 11:15"));
+
+		let unknown = read_pdn("[GameType \"25\"] *").unwrap();
+		assert_eq!(
+			Err(PdnError::UnsupportedGameType{game_type : "25".to_string()}),
+			unknown[0].replay().map(|_| ()));
+	}
+
+	#[test]
+	fn write_and_read_round_trip() {
+		let mut game = Game::with_variant(Variant::American);
+		game.set_first_player(2);
+		let moves = vec![
+			vec![BoardPosition::new(5, 5), BoardPosition::new(4, 4)],
+			vec![BoardPosition::new(2, 2), BoardPosition::new(3, 3)],
+			vec![BoardPosition::new(4, 4), BoardPosition::new(2, 2)]];
+		for path in moves {
+			let the_move = game.find_move(&path).unwrap();
+			game.apply(the_move).unwrap();
+		}
+
+		let mut pdn_game = PdnGame::from_game(&game).unwrap();
+		pdn_game.set_tag("White", "Ann");
+		assert_eq!(None, pdn_game.tag("FEN"));
+
+		let pdn = pdn_game.to_pdn();
+		assert!(pdn.contains("[White \"Ann\"]\n"));
+		assert!(pdn.ends_with("1. 11-15 22-18 2. 15x22 *\n"));
+
+		let read_games = read_pdn(&pdn).unwrap();
+		assert_eq!(vec![pdn_game], read_games);
+		assert_eq!(game.moves(), read_games[0].replay().unwrap().moves());
+	}
+
+	#[test]
+	fn write_fen_for_other_starting_positions() {
+		let game = Game::new();
+		let pdn_game = PdnGame::from_game(&game).unwrap();
+		assert_eq!(
+			Some("W:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,12"),
+			pdn_game.tag("FEN"));
+		assert_eq!(1, pdn_game.replay().unwrap().current_player().id);
+
+		let giveaway = Game::with_variant(Variant::Giveaway);
+		assert_eq!(
+			Err(PdnError::UnsupportedVariant{variant : Variant::Giveaway}),
+			PdnGame::from_game(&giveaway));
+	}
+
+	#[test]
+	fn results_decided_off_the_board_are_replayed() {
+		let mut resigned = Game::with_variant(Variant::American);
+		resigned.set_first_player(2);
+		let the_move = resigned.find_move(
+			&[BoardPosition::new(5, 1), BoardPosition::new(4, 0)]).unwrap();
+		resigned.apply(the_move).unwrap();
+		resigned.resign(1).unwrap();
+
+		let mut drawn = Game::with_variant(Variant::American);
+		drawn.set_first_player(2);
+		drawn.offer_draw(2).unwrap();
+		drawn.accept_draw(1).unwrap();
+
+		for game in &[resigned, drawn] {
+			let pdn = PdnGame::from_game(game).unwrap().to_pdn();
+			let replayed = read_pdn(&pdn).unwrap()[0].replay().unwrap();
+			assert_eq!(game.state(), replayed.state());
+			assert_eq!(pdn, PdnGame::from_game(&replayed).unwrap().to_pdn());
+		}
+	}
+
+	#[test]
+	fn record_and_replay_handicap() {
+		let handicap = Handicap::new(2)
+			.remove(BoardPosition::new(7, 1))
+			.extra_king(BoardPosition::new(3, 3))
+			.extra_time(Duration::from_secs(60));
+		let mut game = Game::new();
+		game.set_handicap(handicap.clone()).unwrap();
+		let the_move = game.find_move(
+			&[BoardPosition::new(3, 3), BoardPosition::new(4, 4)]).unwrap();
+		game.apply(the_move).unwrap();
+
+		let pdn_game = PdnGame::from_game(&game).unwrap();
+		assert_eq!(Some("B:R1:K18:T60"), pdn_game.tag("Handicap"));
+
+		let replayed = read_pdn(&pdn_game.to_pdn()).unwrap()[0].replay().unwrap();
+		assert_eq!(Some(&handicap), replayed.handicap());
+		assert_eq!(game.starting_pieces(), replayed.starting_pieces());
+		assert_eq!(game.moves(), replayed.moves());
+
+		let mut pdn_game = pdn_game;
+		pdn_game.set_tag("Handicap", "B:K18:X1");
+		assert_eq!(
+			Err(PdnError::InvalidHandicap{handicap : "B:K18:X1".to_string()}),
+			pdn_game.replay().map(|_| ()));
+	}
+
+	#[test]
+	fn result_must_match_final_position() {
+		let pdn = "[FEN \"B:W18:B14\"]\n1. 14x23 1-0";
+		let games = read_pdn(pdn).unwrap();
+		assert_eq!(
+			Err(PdnError::ResultMismatch{result : "1-0".to_string()}),
+			games[0].replay().map(|_| ()));
+
+		let pdn = "[FEN \"B:W18:B14\"]\n1. 14x23 0-1";
+		let game = read_pdn(pdn).unwrap()[0].replay().unwrap();
+		assert_eq!(
+			GameState::GameOver{
+				winner_id : 2, reason : WinReason::AllPiecesCaptured, move_number : 1},
+			*game.state());
+	}
+}
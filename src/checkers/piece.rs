@@ -1,5 +1,7 @@
 use checkers::player::Player;
 
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
 pub enum PieceType {
 	Man,
 	King
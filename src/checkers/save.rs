@@ -0,0 +1,421 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use checkers::ai::Move;
+use checkers::board::BoardPosition;
+use checkers::display::format_position;
+use checkers::game::{
+	DrawReason,
+	DrawRules,
+	Game,
+	GameSnapshot,
+	GameState,
+	SnapshotError,
+	WinReason};
+use checkers::handicap::Handicap;
+use checkers::input::parse_position;
+use checkers::piece::PieceType;
+use checkers::variant::Variant;
+
+/// The version of the save format written by `write_save`
+pub const SAVE_FORMAT_VERSION : u32 = 1;
+
+const SAVE_HEADER : &'static str = "rusty-checkers save";
+
+/// The reasons a saved game cannot be read
+#[derive(Debug, PartialEq, Eq)]
+pub enum SaveError {
+	/// The text does not start with the `rusty-checkers save` header
+	NotASave,
+
+	/// The game was saved in a version of the format that cannot be read
+	UnsupportedVersion{ version : String },
+
+	/// The line numbered `line_number`, counting from 1, cannot be read
+	Malformed{ line_number : usize },
+
+	/// A line the save must have is missing, e.g. `variant`
+	Missing{ key : &'static str },
+
+	/// The game cannot be restored from the save
+	Snapshot(SnapshotError)
+}
+
+fn format_positions(positions : &[BoardPosition]) -> String {
+	positions.iter()
+		.map(|position| format_position(*position))
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+// a move is written as its path, followed by `x`
+// and the captured positions if it is a jump
+fn format_save_move(the_move : &Move) -> String {
+	if the_move.is_jump() {
+		format!("{} x {}", format_positions(the_move.path()), format_positions(the_move.captured()))
+	} else {
+		format_positions(the_move.path())
+	}
+}
+
+fn format_state(state : &GameState) -> String {
+	match *state {
+		GameState::InProgress => "in-progress".to_string(),
+		GameState::GameOver{winner_id, reason, move_number} => {
+			let reason = match reason {
+				WinReason::AllPiecesCaptured => "captured",
+				WinReason::AllPiecesBlocked => "blocked",
+				WinReason::Resignation => "resignation",
+				WinReason::Time => "time"
+			};
+			format!("win {} {} {}", winner_id, reason, move_number)
+		},
+		GameState::Draw{reason, move_number} => {
+			let reason = match reason {
+				DrawReason::Repetition => "repetition",
+				DrawReason::MoveLimit => "move-limit",
+				DrawReason::Agreement => "agreement"
+			};
+			format!("draw {} {}", reason, move_number)
+		}
+	}
+}
+
+///
+/// Writes `game` in the save format read by `read_save`. The first line
+/// names the format and its version, and every other line holds a key
+/// followed by its values, e.g. `move c3 d4`. A save holds how the game
+/// started, the moves made since and how the game stands.
+///
+pub fn write_save(game : &Game) -> String {
+	let snapshot = game.snapshot();
+	let mut lines = vec![
+		format!("{} {}", SAVE_HEADER, SAVE_FORMAT_VERSION),
+		format!("variant {}", snapshot.variant.name()),
+		format!("size {}", snapshot.board_size),
+		format!("repetitions {}", snapshot.draw_rules.repetitions.unwrap_or(0)),
+		format!("move-limit {}", snapshot.draw_rules.move_limit.unwrap_or(0))];
+
+	if let Some(ref handicap) = snapshot.handicap {
+		lines.push(format!("handicap {}", handicap.player_id));
+		if !handicap.removed.is_empty() {
+			lines.push(format!("handicap-remove {}", format_positions(&handicap.removed)));
+		}
+		if !handicap.extra_kings.is_empty() {
+			lines.push(format!("handicap-kings {}", format_positions(&handicap.extra_kings)));
+		}
+		if handicap.extra_time > Duration::from_secs(0) {
+			lines.push(format!("handicap-time {}", handicap.extra_time.as_secs()));
+		}
+	}
+
+	lines.push(format!("first {}", snapshot.first_player_id));
+	for player_id in 1..3 {
+		for &(key, piece_type) in &[("men", PieceType::Man), ("kings", PieceType::King)] {
+			let positions : Vec<_> = snapshot.starting_pieces.iter()
+				.filter(|&&(_, owner_id, owner_type)| owner_id == player_id && owner_type == piece_type)
+				.map(|&(position, _, _)| position)
+				.collect();
+			if !positions.is_empty() {
+				lines.push(format!("{} {} {}", key, player_id, format_positions(&positions)));
+			}
+		}
+	}
+
+	for the_move in &snapshot.moves {
+		lines.push(format!("move {}", format_save_move(the_move)));
+	}
+	for the_move in &snapshot.undone_moves {
+		lines.push(format!("undone {}", format_save_move(the_move)));
+	}
+
+	if let Some(player_id) = snapshot.draw_offer {
+		lines.push(format!("draw-offer {}", player_id));
+	}
+	lines.push(format!("side-to-move {}", snapshot.side_to_move));
+	lines.push(format!("state {}", format_state(&snapshot.state)));
+
+	let mut save = lines.join("\n");
+	save.push('\n');
+	save
+}
+
+fn parse_save_positions(values : &[&str]) -> Option<Vec<BoardPosition>> {
+	values.iter()
+		.map(|value| parse_position(value).ok())
+		.collect()
+}
+
+fn parse_save_move(values : &[&str]) -> Option<Move> {
+	let (path, captured) = match values.iter().position(|value| *value == "x") {
+		Some(index) => (&values[..index], &values[index + 1..]),
+		None => (values, &[][..])
+	};
+	let is_jump = path.len() < values.len();
+	if path.len() < 2 || is_jump == captured.is_empty() {
+		return None;
+	}
+
+	match (parse_save_positions(path), parse_save_positions(captured)) {
+		(Some(path), Some(captured)) => Some(Move::new(path, captured)),
+		_ => None
+	}
+}
+
+// reads the ID of one of the two players, `1` or `2`
+fn parse_player_id(value : &str) -> Option<u32> {
+	match value.parse::<u32>() {
+		Ok(player_id) if player_id == 1 || player_id == 2 => Some(player_id),
+		_ => None
+	}
+}
+
+fn parse_state(values : &[&str]) -> Option<GameState> {
+	if values == ["in-progress"] {
+		return Some(GameState::InProgress);
+	}
+
+	let move_number = match values.last().map(|value| value.parse::<usize>()) {
+		Some(Ok(move_number)) => move_number,
+		_ => return None
+	};
+	match (values[0], values.len()) {
+		("win", 4) => {
+			let reason = match values[2] {
+				"captured" => WinReason::AllPiecesCaptured,
+				"blocked" => WinReason::AllPiecesBlocked,
+				"resignation" => WinReason::Resignation,
+				"time" => WinReason::Time,
+				_ => return None
+			};
+			parse_player_id(values[1]).map(|winner_id| GameState::GameOver{
+				winner_id : winner_id,
+				reason : reason,
+				move_number : move_number})
+		},
+		("draw", 3) => {
+			let reason = match values[1] {
+				"repetition" => DrawReason::Repetition,
+				"move-limit" => DrawReason::MoveLimit,
+				"agreement" => DrawReason::Agreement,
+				_ => return None
+			};
+			Some(GameState::Draw{reason : reason, move_number : move_number})
+		},
+		_ => None
+	}
+}
+
+// reads a single value, e.g. the `3` of `repetitions 3`
+fn parse_value<T : FromStr>(values : &[&str]) -> Option<T> {
+	if values.len() == 1 {
+		values[0].parse().ok()
+	} else {
+		None
+	}
+}
+
+///
+/// Reads a game written by `write_save`, and restores it by making its
+/// moves again. Empty lines and lines starting with `#` are skipped.
+///
+pub fn read_save(text : &str) -> Result<Game, SaveError> {
+	let mut lines = text.lines()
+		.enumerate()
+		.map(|(index, line)| (index + 1, line.trim()))
+		.filter(|&(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+	match lines.next() {
+		Some((_, header)) if header.starts_with(SAVE_HEADER) => {
+			let version = header[SAVE_HEADER.len()..].trim();
+			if version != SAVE_FORMAT_VERSION.to_string() {
+				return Err(SaveError::UnsupportedVersion{version : version.to_string()});
+			}
+		},
+		_ => return Err(SaveError::NotASave)
+	}
+
+	let mut variant = None;
+	let mut board_size = None;
+	let mut draw_rules = DrawRules::standard();
+	let mut handicap : Option<Handicap> = None;
+	let mut first_player_id = None;
+	let mut starting_pieces = Vec::new();
+	let mut moves = Vec::new();
+	let mut undone_moves = Vec::new();
+	let mut draw_offer = None;
+	let mut side_to_move = None;
+	let mut state = None;
+
+	for (line_number, line) in lines {
+		let malformed = SaveError::Malformed{line_number : line_number};
+		let words : Vec<_> = line.split_whitespace().collect();
+		let (key, values) = (words[0], &words[1..]);
+
+		// the handicap lines follow the `handicap` line
+		let handicap_result = match key {
+			"handicap-remove" | "handicap-kings" | "handicap-time" => match handicap {
+				Some(ref mut handicap) => Some(match key {
+					"handicap-remove" => parse_save_positions(values)
+						.map(|positions| handicap.removed.extend(positions)),
+					"handicap-kings" => parse_save_positions(values)
+						.map(|positions| handicap.extra_kings.extend(positions)),
+					_ => parse_value(values)
+						.map(|seconds| handicap.extra_time = Duration::from_secs(seconds))
+				}),
+				None => Some(None)
+			},
+			_ => None
+		};
+
+		let parsed = match (key, handicap_result) {
+			(_, Some(result)) => result,
+			("variant", _) => parse_value::<String>(values)
+				.and_then(|name| Variant::from_name(&name))
+				.map(|value| variant = Some(value)),
+			("size", _) => parse_value(values).map(|value| board_size = Some(value)),
+			("repetitions", _) => parse_value(values).map(|value| draw_rules.repetitions =
+				if value == 0 { None } else { Some(value) }),
+			("move-limit", _) => parse_value(values).map(|value| draw_rules.move_limit =
+				if value == 0 { None } else { Some(value) }),
+			("handicap", _) => parse_value(values)
+				.map(|player_id| handicap = Some(Handicap::new(player_id))),
+			("first", _) => parse_value(values).map(|value| first_player_id = Some(value)),
+			("men", _) | ("kings", _) if values.len() >= 2 => {
+				let piece_type = if key == "men" { PieceType::Man } else { PieceType::King };
+				values[0].parse::<u32>().ok()
+					.and_then(|player_id| parse_save_positions(&values[1..])
+						.map(|positions| starting_pieces.extend(positions.into_iter()
+							.map(|position| (position, player_id, piece_type)))))
+			},
+			("move", _) => parse_save_move(values).map(|the_move| moves.push(the_move)),
+			("undone", _) => parse_save_move(values).map(|the_move| undone_moves.push(the_move)),
+			("draw-offer", _) => parse_value::<String>(values)
+				.and_then(|value| parse_player_id(&value))
+				.map(|value| draw_offer = Some(value)),
+			("side-to-move", _) => parse_value::<String>(values)
+				.and_then(|value| parse_player_id(&value))
+				.map(|value| side_to_move = Some(value)),
+			("state", _) => parse_state(values).map(|value| state = Some(value)),
+			_ => None
+		};
+
+		if parsed.is_none() {
+			return Err(malformed);
+		}
+	}
+
+	let snapshot = GameSnapshot{
+		variant : try!(variant.ok_or(SaveError::Missing{key : "variant"})),
+		board_size : try!(board_size.ok_or(SaveError::Missing{key : "size"})),
+		starting_pieces : starting_pieces,
+		first_player_id : try!(first_player_id.ok_or(SaveError::Missing{key : "first"})),
+		handicap : handicap,
+		draw_rules : draw_rules,
+		moves : moves,
+		undone_moves : undone_moves,
+		draw_offer : draw_offer,
+		side_to_move : try!(side_to_move.ok_or(SaveError::Missing{key : "side-to-move"})),
+		state : try!(state.ok_or(SaveError::Missing{key : "state"}))};
+
+	Game::from_snapshot(snapshot).map_err(SaveError::Snapshot)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use std::time::Duration;
+
+	use checkers::board::BoardPosition;
+	use checkers::game::{Game, GameState, SnapshotError, WinReason};
+	use checkers::handicap::Handicap;
+	use checkers::variant::Variant;
+
+	#[test]
+	fn write_game_in_progress() {
+		let mut game = Game::new();
+		game.play(&[(2, 2), (3, 3)]);
+		game.play(&[(5, 5), (4, 4)]);
+		game.play(&[(3, 3), (5, 5)]);
+		game.undo();
+
+		let save = write_save(&game);
+		let lines : Vec<_> = save.lines().collect();
+		assert_eq!("rusty-checkers save 1", lines[0]);
+		assert_eq!(
+			vec![
+				"move c3 d4",
+				"move f6 e5",
+				"undone d4 f6 x e5",
+				"side-to-move 1",
+				"state in-progress"],
+			&lines[lines.len() - 5..]);
+		assert!(lines.contains(&"men 1 a1 c1 e1 g1 b2 d2 f2 h2 a3 c3 e3 g3"));
+
+		let mut restored = read_save(&save).unwrap();
+		assert_eq!(game.snapshot(), restored.snapshot());
+		assert!(restored.redo());
+		assert_eq!(1, restored.statistics().players[0].captured);
+	}
+
+	#[test]
+	fn round_trip_of_finished_handicap_game() {
+		let mut game = Game::with_variant(Variant::Italian);
+		game.set_handicap(Handicap::new(2)
+			.remove(BoardPosition::new(7, 2))
+			.extra_time(Duration::from_secs(120))).unwrap();
+		game.play(&[(2, 1), (3, 2)]);
+		game.offer_draw(2).unwrap();
+		game.resign(2).unwrap();
+
+		let save = write_save(&game);
+		assert!(save.contains("handicap 2\nhandicap-remove c8\nhandicap-time 120\n"));
+		assert!(save.ends_with("state win 1 resignation 1\n"));
+
+		let restored = read_save(&save).unwrap();
+		assert_eq!(game.snapshot(), restored.snapshot());
+		assert_eq!(Variant::Italian, restored.variant());
+		match *restored.state() {
+			GameState::GameOver{reason : WinReason::Resignation, ..} => { },
+			ref state => panic!("unexpected state {:?}", state)
+		}
+	}
+
+	#[test]
+	fn reject_bad_saves() {
+		assert_eq!(SaveError::NotASave, read_save("variant American\n").err().unwrap());
+		assert_eq!(
+			SaveError::UnsupportedVersion{version : "2".to_string()},
+			read_save("rusty-checkers save 2\n").err().unwrap());
+
+		let save = write_save(&Game::new());
+		assert_eq!(
+			SaveError::Malformed{line_number : 3},
+			read_save(&save.replace("size 8", "size eight")).err().unwrap());
+		assert_eq!(
+			SaveError::Missing{key : "state"},
+			read_save(&save.replace("state in-progress", "")).err().unwrap());
+
+		let illegal = save.replace("side-to-move 1", "move a3 b5\nside-to-move 2");
+		assert_eq!(
+			SaveError::Snapshot(SnapshotError::IllegalMove{index : 0}),
+			read_save(&illegal).err().unwrap());
+
+		// the state is the last line, after the side to move
+		let state_line = save.lines().count();
+		let unknown_winner = save.replace("state in-progress", "state win 7 resignation 1");
+		assert_eq!(
+			SaveError::Malformed{line_number : state_line},
+			read_save(&unknown_winner).err().unwrap());
+		assert_eq!(
+			SaveError::Malformed{line_number : state_line - 1},
+			read_save(&save.replace("side-to-move 1", "side-to-move 3")).err().unwrap());
+	}
+
+	#[test]
+	fn comments_and_empty_lines_are_skipped() {
+		let save = write_save(&Game::new()).replace("\nfirst", "\n\n# the first player\nfirst");
+		assert!(read_save(&save).is_ok());
+	}
+}
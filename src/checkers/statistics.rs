@@ -0,0 +1,39 @@
+/// The material and captures of one player
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct PlayerStatistics {
+	pub player_id : u32,
+
+	/// The number of men and kings the player has on the board
+	pub men : usize,
+	pub kings : usize,
+
+	/// The number of opposing pieces the player has captured
+	pub captured : usize,
+
+	/// The most pieces the player has captured in a single move
+	pub longest_jump : usize
+}
+
+impl PlayerStatistics {
+	/// The number of pieces the player has on the board
+	pub fn pieces(&self) -> usize {
+		self.men + self.kings
+	}
+}
+
+/// Statistics about a game so far, as reported by `Game::statistics`
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct GameStatistics {
+	pub players : [PlayerStatistics; 2],
+
+	/// The number of moves made by both players
+	pub moves_made : usize,
+
+	/// The number of the current move, starting at 1 and
+	/// increasing once both players have moved
+	pub move_number : usize,
+
+	/// The number of moves made by both players since
+	/// the last capture, or since the start of the game
+	pub moves_since_capture : usize
+}
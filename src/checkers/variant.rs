@@ -0,0 +1,275 @@
+use checkers::ai::{CaptureRules, Direction, Movement, MoveVector};
+use checkers::board::{Board, Orientation};
+use checkers::player::Player;
+
+/// Describes how competing jump moves are ranked against each
+/// other. Only the highest ranked jumps may be made.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum CapturePriority {
+	/// Any available jump may be made
+	None,
+
+	/// The jumps capturing the most pieces must be made
+	MostPieces,
+
+	/// Italian draughts priority: capture the most pieces, then
+	/// capture with a king, then capture the most kings, then
+	/// capture a king as early as possible
+	Italian
+}
+
+/// Describes who wins once the player to move has no moves left,
+/// either because all of their pieces were captured or because all
+/// of their pieces are blocked
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum WinCondition {
+	/// The player who cannot move loses
+	NoMovesLoses,
+
+	/// The player who cannot move wins. Players try to
+	/// give away all of their pieces.
+	NoMovesWins
+}
+
+/// The set of rules a game of checkers is played with
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub enum Variant {
+	/// American checkers, also known as English draughts
+	American,
+
+	/// Italian draughts
+	Italian,
+
+	/// Turkish draughts, where pieces move orthogonally
+	/// and every tile of the board is used
+	Turkish,
+
+	/// Canadian checkers, played on a 12x12 board with
+	/// flying kings and men that jump backwards
+	Canadian,
+
+	/// Giveaway checkers, also known as anti-checkers. The
+	/// pieces move as in American checkers, but the player
+	/// who cannot move wins.
+	Giveaway
+}
+
+impl Variant {
+	/// Looks up a variant by its case insensitive name,
+	/// e.g. `"american"` or `"italian"`
+	pub fn from_name(name : &str) -> Option<Variant> {
+		match &*name.to_lowercase() {
+			"american" => Some(Variant::American),
+			"italian" => Some(Variant::Italian),
+			"turkish" => Some(Variant::Turkish),
+			"canadian" => Some(Variant::Canadian),
+			"giveaway" => Some(Variant::Giveaway),
+			_ => None
+		}
+	}
+
+	pub fn name(&self) -> &'static str {
+		match *self {
+			Variant::American => "American",
+			Variant::Italian => "Italian",
+			Variant::Turkish => "Turkish",
+			Variant::Canadian => "Canadian",
+			Variant::Giveaway => "Giveaway"
+		}
+	}
+
+	/// The number of rows and columns of the regulation board
+	pub fn board_size(&self) -> usize {
+		match *self {
+			Variant::American | Variant::Italian | Variant::Turkish | Variant::Giveaway => 8,
+			Variant::Canadian => 12
+		}
+	}
+
+	/// The number of rows each player fills with men at the
+	/// start of a game on the regulation board
+	pub fn starting_rows(&self) -> usize {
+		match *self {
+			Variant::American | Variant::Italian | Variant::Giveaway => 3,
+			Variant::Turkish => 2,
+			Variant::Canadian => 5
+		}
+	}
+
+	/// Creates a `size` by `size` board in its starting position, where
+	/// `player1` fills the lowest `starting_rows` ranks and `player2`
+	/// fills the highest `starting_rows` ranks.
+	///
+	/// # Panics
+	///
+	/// Panics under the same conditions as `Board::new_sized_checkerboard`.
+	pub fn new_board
+	(&self,
+			size : usize,
+			starting_rows : usize,
+			player1 : &Player,
+			player2 : &Player)
+	-> Board {
+		match *self {
+			Variant::American | Variant::Canadian | Variant::Giveaway =>
+				Board::new_sized_checkerboard(
+					size, starting_rows, player1, player2, Orientation::DarkCorner),
+			Variant::Italian =>
+				Board::new_sized_checkerboard(
+					size, starting_rows, player1, player2, Orientation::LightCorner),
+			Variant::Turkish =>
+				Board::new_sized_full_board(size, starting_rows, player1, player2)
+		}
+	}
+
+	/// Whether pieces may stand on the tile at `row` and `column` of the
+	/// boards created by `new_board`
+	pub fn is_playable_tile(&self, row : usize, column : usize) -> bool {
+		match *self {
+			Variant::American | Variant::Canadian | Variant::Giveaway =>
+				(row + column) % 2 == 0,
+			Variant::Italian => (row + column) % 2 == 1,
+			Variant::Turkish => true
+		}
+	}
+
+	/// How a man moving in `direction` moves and captures
+	pub fn man_movement(&self, direction : Direction) -> Movement {
+		match *self {
+			Variant::American | Variant::Giveaway => Movement::diagonal_man(direction),
+			Variant::Italian =>
+				// men may not capture kings
+				Movement{ captures_kings : false, .. Movement::diagonal_man(direction) },
+			Variant::Turkish => Movement::orthogonal_man(direction),
+			Variant::Canadian =>
+				// men jump backwards as well as forwards
+				Movement{
+					capture_steps : MoveVector::diagonals(),
+					.. Movement::diagonal_man(direction)}
+		}
+	}
+
+	/// How a king moves and captures
+	pub fn king_movement(&self) -> Movement {
+		match *self {
+			Variant::American | Variant::Italian | Variant::Giveaway =>
+				Movement::diagonal_king(),
+			Variant::Turkish => Movement::orthogonal_king(),
+			Variant::Canadian => Movement{ flying : true, .. Movement::diagonal_king() }
+		}
+	}
+
+	pub fn capture_rules(&self) -> CaptureRules {
+		match *self {
+			Variant::American | Variant::Italian | Variant::Canadian | Variant::Giveaway =>
+				CaptureRules::american(),
+			Variant::Turkish =>
+				CaptureRules{ remove_immediately : true, allow_reversal : false }
+		}
+	}
+
+	/// Whether a jump move must be continued until no more
+	/// jumps are available. When false, players are only
+	/// required to make the first jump of a sequence.
+	pub fn requires_complete_jumps(&self) -> bool {
+		match *self {
+			Variant::American | Variant::Giveaway => false,
+			Variant::Italian | Variant::Turkish | Variant::Canadian => true
+		}
+	}
+
+	pub fn capture_priority(&self) -> CapturePriority {
+		match *self {
+			Variant::American | Variant::Giveaway => CapturePriority::None,
+			Variant::Italian => CapturePriority::Italian,
+			Variant::Turkish | Variant::Canadian => CapturePriority::MostPieces
+		}
+	}
+
+	/// The number identifying the variant in the `GameType` tag of
+	/// Portable Draughts Notation, if the variant has one
+	pub fn pdn_game_type(&self) -> Option<u32> {
+		match *self {
+			Variant::American => Some(21),
+			Variant::Italian => Some(22),
+			Variant::Canadian => Some(27),
+			Variant::Turkish => Some(30),
+			Variant::Giveaway => None
+		}
+	}
+
+	/// Looks up a variant by the number identifying it in
+	/// the `GameType` tag of Portable Draughts Notation
+	pub fn from_pdn_game_type(game_type : u32) -> Option<Variant> {
+		match game_type {
+			21 => Some(Variant::American),
+			22 => Some(Variant::Italian),
+			27 => Some(Variant::Canadian),
+			30 => Some(Variant::Turkish),
+			_ => None
+		}
+	}
+
+	/// Who wins once the player to move has no moves left. Anything
+	/// choosing moves for a player should consult this to know whether
+	/// losing pieces is good or bad.
+	pub fn win_condition(&self) -> WinCondition {
+		match *self {
+			Variant::American | Variant::Italian | Variant::Turkish | Variant::Canadian =>
+				WinCondition::NoMovesLoses,
+			Variant::Giveaway => WinCondition::NoMovesWins
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn variant_names_round_trip() {
+		let variants = [
+			Variant::American,
+			Variant::Italian,
+			Variant::Turkish,
+			Variant::Canadian,
+			Variant::Giveaway];
+		for variant in &variants {
+			assert_eq!(Some(*variant), Variant::from_name(variant.name()));
+		}
+	}
+
+	#[test]
+	fn pdn_game_types_round_trip() {
+		let variants = [
+			Variant::American,
+			Variant::Italian,
+			Variant::Turkish,
+			Variant::Canadian];
+		for variant in &variants {
+			let game_type = variant.pdn_game_type().unwrap();
+			assert_eq!(Some(*variant), Variant::from_pdn_game_type(game_type));
+		}
+		assert_eq!(None, Variant::Giveaway.pdn_game_type());
+	}
+
+	#[test]
+	fn unknown_variant_name() {
+		assert_eq!(None, Variant::from_name("klingon"));
+	}
+
+	#[test]
+	fn playable_tiles_match_new_board() {
+		let player1 = Player{id : 1};
+		let player2 = Player{id : 2};
+		let variants = [Variant::American, Variant::Italian, Variant::Turkish];
+		for variant in &variants {
+			let board = variant.new_board(8, 2, &player1, &player2);
+			for c in 0..8 {
+				let occupied = board.get_tile(1, c).get_piece().is_some();
+				assert_eq!(occupied, variant.is_playable_tile(1, c));
+			}
+		}
+	}
+}
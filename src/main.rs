@@ -1,134 +1,978 @@
 #[macro_use]
 extern crate corrosion;
 
-use std::io::{stdin, stdout, Write};
+#[cfg(feature = "serialization")]
+extern crate serde;
+
+#[cfg(feature = "serialization")]
+#[macro_use]
+extern crate serde_derive;
+
+#[cfg(all(test, feature = "serialization"))]
+extern crate serde_json;
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{stdin, stdout, BufReader, Read, Write};
+use std::process;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 mod checkers;
 use checkers::{
+	ActionError,
 	BoardPosition,
+	Clock,
+	ColorScheme,
+	Command,
+	CommandError,
+	DrawReason,
+	DrawRules,
+	FenError,
 	Game,
+	GameEvent,
 	GameState,
+	Handicap,
 	InputError,
 	MoveError,
-	SimpleMove,
-	TokenError};
+	Notation,
+	PdnError,
+	PdnGame,
+	PlayerColor,
+	SaveError,
+	SetupError,
+	SnapshotError,
+	TimeBonus,
+	TimeControl,
+	TokenError,
+	Variant,
+	WinReason};
 
-mod util;
+mod batch;
 
 fn apply_positions_as_move
 (game : &mut Game, positions : Vec<BoardPosition>)
 -> Result<GameState, MoveError> {
-	if positions.len() == 2 {
-		let start = positions[0];
-		let end = positions[1];
-		
-		let row_diff = util::absolute_diff(start.row, end.row);
-		let col_diff = util::absolute_diff(start.column, end.column);
-		
-		if row_diff == 1 && col_diff == 1 {
-			game.apply_simple_move(SimpleMove::new(
-				start.row, start.column, end.row, end.column))
-		} else {
-			game.apply_jump_move(positions)
+	let the_move = try!(game.find_move(&positions));
+	game.apply(the_move)
+}
+
+// reports every move made in `game`, including the moves made again
+// by `redo`, and the men they crown, e.g. "Red played c3 d4"
+fn report_moves(game : &mut Game, colors : &ColorScheme, notation : Notation) {
+	let colors = colors.clone();
+	let variant = game.variant();
+	let size = game.board().number_rows();
+	game.add_observer(Box::new(move |event : &GameEvent| match *event {
+		GameEvent::MoveMade{player_id, ref the_move} => println!("\n{} played {}",
+			colors.name(player_id), checkers::format_move(the_move, notation, variant, size)),
+		GameEvent::PieceCrowned{player_id, position} => println!("{}'s man is crowned on {}",
+			colors.name(player_id), checkers::format_position(position)),
+		_ => { }
+	}));
+}
+
+// prints the final result of the game, e.g.
+// "Black wins: Red has no legal moves (blocked)"
+fn print_game_result(game : &Game, colors : &ColorScheme) {
+	let (result, move_number) = match *game.state() {
+		GameState::InProgress => return,
+		GameState::GameOver{winner_id, reason, move_number} => {
+			let loser_id = if winner_id == 1 { 2 } else { 1 };
+			// the player to move is the one who could not move, who
+			// wins in giveaway checkers
+			let player_to_move = colors.name(game.current_player().id);
+			
+			let reason = match reason {
+				WinReason::AllPiecesCaptured =>
+					format!("{} has no pieces left (all captured)", player_to_move),
+				WinReason::AllPiecesBlocked =>
+					format!("{} has no legal moves (blocked)", player_to_move),
+				WinReason::Resignation => format!("{} resigned", colors.name(loser_id)),
+				WinReason::Time => format!("{} ran out of time", colors.name(loser_id))
+			};
+			
+			(format!("{} wins: {}", colors.name(winner_id), reason), move_number)
+		},
+		GameState::Draw{reason, move_number} => {
+			let draw_rules = game.draw_rules();
+			let reason = match reason {
+				DrawReason::Repetition => match draw_rules.repetitions {
+					Some(repetitions) => format!(
+						"the same position occurred {} times", repetitions),
+					None => "the same position occurred again".to_string()
+				},
+				DrawReason::MoveLimit => match draw_rules.move_limit {
+					Some(move_limit) => format!(
+						"{} moves each without a capture or a man move", move_limit),
+					None => "too many moves without a capture or a man move".to_string()
+				},
+				DrawReason::Agreement => "both players agreed".to_string()
+			};
+			
+			(format!("Draw: {}", reason), move_number)
 		}
-	} else {
-		game.apply_jump_move(positions)
+	};
+	
+	println!("\nGame over after move {}. {}", move_number, result);
+}
+
+// prints a line summarizing the material and moves under the board
+fn print_summary(game : &Game, colors : &ColorScheme) {
+	let statistics = game.statistics();
+	for player in &statistics.players {
+		print!("{}: {} men, {} kings | ", colors.name(player.player_id), player.men, player.kings);
+	}
+	println!("Move {} | {} moves since a capture",
+		statistics.move_number, statistics.moves_since_capture);
+}
+
+// prints the statistics of a finished game
+fn print_report(game : &Game, colors : &ColorScheme) {
+	let statistics = game.statistics();
+	println!("\nMoves made: {}", statistics.moves_made);
+	for player in &statistics.players {
+		println!("{}: {} pieces left ({} men, {} kings) | {} captured | longest jump {}",
+			colors.name(player.player_id),
+			player.pieces(),
+			player.men,
+			player.kings,
+			player.captured,
+			player.longest_jump);
+	}
+}
+
+// prints the odds given in a handicap game, e.g.
+// "Black gives odds to Red: Black starts without b8, d8"
+fn print_handicap(handicap : &Handicap, colors : &ColorScheme) {
+	let stronger = colors.name(handicap.player_id);
+	let weaker = colors.name(handicap.weaker_player_id());
+	
+	let format_positions = |positions : &[BoardPosition]| positions.iter()
+		.map(|position| checkers::format_position(*position))
+		.collect::<Vec<_>>()
+		.join(", ");
+	
+	println!("{} gives odds to {}:", stronger, weaker);
+	if !handicap.removed.is_empty() {
+		println!("  {} starts without {}", stronger, format_positions(&handicap.removed));
 	}
+	if !handicap.extra_kings.is_empty() {
+		println!("  {} starts with extra kings on {}",
+			weaker, format_positions(&handicap.extra_kings));
+	}
+	if handicap.extra_time > Duration::new(0, 0) {
+		println!("  {} starts with {} extra time",
+			weaker, checkers::format_clock_time(handicap.extra_time));
+	}
+}
+
+fn setup_error_message(error : SetupError, colors : &ColorScheme) -> String {
+	match error {
+		SetupError::OffBoard{position} => format!(
+			"{} is not on the board", checkers::format_position(position)),
+		SetupError::UnplayableTile{position} => format!(
+			"{} is not a playable tile", checkers::format_position(position)),
+		SetupError::Occupied{position} => format!(
+			"{} is already occupied", checkers::format_position(position)),
+		SetupError::MissingPiece{position} => format!(
+			"The stronger player has no piece on {} to remove",
+			checkers::format_position(position)),
+		SetupError::NoPieces{player_id} => format!(
+			"{} would start without any pieces", colors.name(player_id)),
+		SetupError::ManOnCrowningRow{position} => format!(
+			"A man cannot start on {}, where it would be crowned",
+			checkers::format_position(position)),
+		SetupError::TooManyPieces{player_id} => format!(
+			"{} has more pieces than at the start of a game", colors.name(player_id)),
+		// the players are named by their colors, and the
+		// handicap is only given at the start of a new game
+		SetupError::UnknownPlayer{..}
+			| SetupError::GameStarted
+			| SetupError::HandicapGiven => unreachable!()
+	}
+}
+
+fn fen_error_message(error : FenError, colors : &ColorScheme) -> String {
+	match error {
+		FenError::Malformed{fen} => format!(
+			"'{}' is not a FEN position such as W:W21,22,K30:B1,2,K5", fen),
+		FenError::InvalidSquare{square} => format!(
+			"'{}' is not a square of the board", square),
+		FenError::Setup(error) => setup_error_message(error, colors)
+	}
+}
+
+fn pdn_error_message(error : PdnError) -> String {
+	match error {
+		PdnError::MalformedTag{tag} => format!("the tag '[{}]' is malformed", tag),
+		PdnError::Unterminated => "a comment or variation is not closed".to_string(),
+		PdnError::InvalidToken{token} => format!("'{}' is not a move", token),
+		PdnError::UnsupportedGameType{game_type} =>
+			format!("game type {} is not supported", game_type),
+		PdnError::UnsupportedVariant{variant} =>
+			format!("{} games cannot be written in PDN", variant.name()),
+		PdnError::Fen(_) => "the FEN tag is not a valid position".to_string(),
+		PdnError::InvalidHandicap{handicap} =>
+			format!("'{}' is not a handicap such as B:R1,3:K30:T60", handicap),
+		PdnError::IllegalMove{index, the_move} =>
+			format!("move {} ({}) is illegal", index / 2 + 1, the_move),
+		PdnError::ResultMismatch{result} =>
+			format!("the result {} does not match the final position", result)
+	}
+}
+
+// reads the first game in a PDN file and replays it
+fn load_pdn_game(path : &str) -> Result<Game, String> {
+	let mut pdn = String::new();
+	try!(File::open(path)
+		.and_then(|mut file| file.read_to_string(&mut pdn))
+		.map_err(|error| format!("Cannot read '{}': {}", path, error)));
+	
+	let games = try!(checkers::read_pdn(&pdn)
+		.map_err(|error| format!("Cannot read '{}': {}", path, pdn_error_message(error))));
+	let pdn_game = try!(games.into_iter().next()
+		.ok_or(format!("'{}' does not contain a game", path)));
+	
+	pdn_game.replay()
+		.map_err(|error| format!("Cannot replay '{}': {}", path, pdn_error_message(error)))
+}
+
+// today's date in the `YYYY.MM.DD` form of the PDN `Date` tag
+fn pdn_date() -> String {
+	let days = SystemTime::now().duration_since(UNIX_EPOCH)
+		.map(|elapsed| elapsed.as_secs() / 86400)
+		.unwrap_or(0);
+	
+	// converts the days since 1970-01-01 to a date of the
+	// Gregorian calendar, in eras of 400 years from 0000-03-01
+	let days = days + 719468;
+	let era = days / 146097;
+	let day_of_era = days % 146097;
+	let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524
+		- day_of_era / 146096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let shifted_month = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+	let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+	let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+	
+	format!("{}.{:02}.{:02}", year, month, day)
+}
+
+// writes the game to a PDN file
+fn record_game(game : &Game, path : &str) -> Result<(), String> {
+	let mut pdn_game = try!(PdnGame::from_game(game)
+		.map_err(|error| format!("Cannot record the game: {}", pdn_error_message(error))));
+	pdn_game.set_tag("Event", "rusty-checkers game");
+	pdn_game.set_tag("Date", &pdn_date());
+	
+	File::create(path)
+		.and_then(|mut file| file.write_all(pdn_game.to_pdn().as_bytes()))
+		.map_err(|error| format!("Cannot write '{}': {}", path, error))
+}
+
+fn save_error_message(error : SaveError, colors : &ColorScheme) -> String {
+	match error {
+		SaveError::NotASave => "it is not a saved game".to_string(),
+		SaveError::UnsupportedVersion{version} => format!(
+			"it was saved in version {} of the format, only version {} can be read",
+			version, checkers::SAVE_FORMAT_VERSION),
+		SaveError::Malformed{line_number} => format!("line {} cannot be read", line_number),
+		SaveError::Missing{key} => format!("the '{}' line is missing", key),
+		SaveError::Snapshot(SnapshotError::BoardSize{size}) =>
+			format!("a game cannot be played on a board of size {}", size),
+		SaveError::Snapshot(SnapshotError::Setup(SetupError::UnknownPlayer{player_id})) =>
+			format!("there is no player {}", player_id),
+		SaveError::Snapshot(SnapshotError::Setup(error)) => setup_error_message(error, colors),
+		SaveError::Snapshot(SnapshotError::IllegalMove{index}) =>
+			format!("move {} is illegal", index + 1),
+		SaveError::Snapshot(SnapshotError::IllegalUndoneMove{index}) =>
+			format!("undone move {} is illegal", index + 1),
+		SaveError::Snapshot(SnapshotError::Inconsistent) =>
+			"the position does not follow from the moves".to_string()
+	}
+}
+
+// reads a game written by `save_game`
+fn load_saved_game(path : &str, colors : &ColorScheme) -> Result<Game, String> {
+	let mut save = String::new();
+	try!(File::open(path)
+		.and_then(|mut file| file.read_to_string(&mut save))
+		.map_err(|error| format!("Cannot read '{}': {}", path, error)));
+	
+	checkers::read_save(&save)
+		.map_err(|error| format!("Cannot load '{}': {}", path, save_error_message(error, colors)))
+}
+
+// writes the game to a save file, which `load_saved_game` reads. The
+// game is written to a temporary file that then replaces the save
+// file, so that a save file is never left half written.
+fn save_game(game : &Game, path : &str) -> Result<(), String> {
+	let temporary_path = format!("{}.tmp", path);
+	File::create(&temporary_path)
+		.and_then(|mut file| file.write_all(checkers::write_save(game).as_bytes())
+			.and_then(|_| file.sync_all()))
+		.and_then(|_| fs::rename(&temporary_path, path))
+		.map_err(|error| format!("Cannot write '{}': {}", path, error))
+}
+
+// saves the game after a move that leaves it in progress, turning
+// autosaving off when the game cannot be saved. A game that has just
+// ended is not saved, so that quitting by accident can be taken back.
+fn autosave(game : &Game, autosave_file : &mut Option<String>) {
+	let autosave_result = autosave_file.as_ref().map(|path| save_game(game, path));
+	if let Some(Err(message)) = autosave_result {
+		println!("\n *** {}, autosaving is off", message);
+		*autosave_file = None;
+	}
+}
+
+fn print_action_error(error : ActionError) {
+	match error {
+		ActionError::DrawAlreadyOffered =>
+			println!("\n *** You have already offered a draw"),
+		ActionError::DrawOfferPending =>
+			println!("\n *** Your opponent has offered a draw, enter 'accept' or 'decline'"),
+		ActionError::NoDrawOffered =>
+			println!("\n *** Your opponent has not offered a draw"),
+		ActionError::GameFinished | ActionError::UnknownPlayer => unreachable!()
+	}
+}
+
+fn print_command_error(error : CommandError) {
+	match error {
+		CommandError::Unknown{command, suggestions} => {
+			println!("\n *** Unknown command '{}'", command);
+			if !suggestions.is_empty() {
+				println!(" *** Did you mean: {}", suggestions.join(", "));
+			}
+			println!(" *** Enter 'help' to list the commands");
+		},
+		CommandError::MissingArgument{command} =>
+			println!("\n *** '{}' needs a file name, e.g. '{} game.pdn'", command, command),
+		CommandError::UnexpectedArgument{command} =>
+			println!("\n *** '{}' does not take an argument", command)
+	}
+}
+
+fn print_help() {
+	println!("\nEnter a move, e.g. 'a3 b4', or one of these commands:");
+	for info in checkers::COMMANDS {
+		let usage = match info.argument {
+			Some(argument) => format!("{} <{}>", info.name, argument),
+			None => info.name.to_string()
+		};
+		println!("  {:<12} {}", usage, info.description);
+	}
+}
+
+fn print_legal_moves(game : &Game, notation : Notation) {
+	let size = game.board().number_rows();
+	let moves : Vec<_> = game.legal_moves().iter()
+		.map(|the_move| checkers::format_move(the_move, notation, game.variant(), size))
+		.collect();
+	println!("\nLegal moves: {}", moves.join(", "));
+}
+
+// applies the resignation or draw command for the player, or
+// returns `None` if the command is not one of these commands
+fn apply_action
+(game : &mut Game, colors : &ColorScheme, player_id : u32, command : &Command)
+-> Option<Result<GameState, ActionError>> {
+	let result = match *command {
+		Command::Quit => {
+			// quitting the game concedes it to the opponent
+			println!("\nGiving up so soon?");
+			game.resign(player_id)
+		},
+		Command::Resign => game.resign(player_id),
+		Command::Draw => game.offer_draw(player_id).map(|_| {
+			println!("\n{} offers a draw. Now make your move.", colors.name(player_id));
+			GameState::InProgress
+		}),
+		Command::Accept => game.accept_draw(player_id),
+		Command::Decline => game.decline_draw(player_id).map(|_| GameState::InProgress),
+		_ => return None
+	};
+	
+	Some(result)
+}
+
+// the options selected on the command line
+struct Options {
+	variant : Variant,
+	board_size : usize,
+	starting_rows : usize,
+	draw_rules : DrawRules,
+	colors : ColorScheme,
+	first_player_id : u32,
+	time_control : Option<TimeControl>,
+	handicap : Option<Handicap>,
+	notation : Notation,
+	
+	// the position the game starts from, in FEN
+	fen : Option<String>,
+	
+	// the PDN file the game is loaded from, and the one
+	// it is recorded to once it is over
+	pdn_file : Option<String>,
+	record_file : Option<String>,
+	
+	// the script of moves played in batch mode,
+	// where `-` reads it from standard input
+	batch_file : Option<String>,
+	
+	// the save file the game is resumed from, and the one it is
+	// saved to after every move, if autosaving is on
+	resume_file : Option<String>,
+	autosave_file : Option<String>
+}
+
+// the file the game is saved to after every move, unless
+// another one is given with `--autosave`
+const DEFAULT_AUTOSAVE_FILE : &'static str = "rusty-checkers-autosave.txt";
+
+fn parse_number_arg(name : &str, value : Option<String>) -> Result<usize, String> {
+	let value = try!(value.ok_or(format!("{} requires a number", name)));
+	value.parse::<usize>()
+		.map_err(|_| format!("{} requires a number, found '{}'", name, value))
+}
+
+// reads the positions from an argument like `b8,d8`
+fn parse_positions_arg(name : &str, value : Option<String>) -> Result<Vec<BoardPosition>, String> {
+	let value = try!(value.ok_or(format!("{} requires board positions, e.g. b8,d8", name)));
+	value.split(',')
+		.map(|token| checkers::parse_position(token)
+			.map_err(|_| format!("{} requires board positions, found '{}'", name, token)))
+		.collect()
+}
+
+// reads the color names from an argument like `black,white`, where
+// the first color plays up the board
+fn parse_colors(names : &str) -> Result<ColorScheme, String> {
+	let names : Vec<_> = names.split(',').map(|name| name.trim()).collect();
+	if names.len() != 2 || names.iter().any(|name| name.is_empty()) {
+		return Err(format!("--colors requires two names, found '{}'", names.join(",")));
+	}
+	
+	let colors = ColorScheme::new(PlayerColor::new(names[0]), PlayerColor::new(names[1]));
+	if colors.player1.man_symbol == colors.player2.man_symbol {
+		return Err(format!("The colors '{}' and '{}' must start with different letters",
+			names[0], names[1]));
+	}
+	
+	Ok(colors)
 }
 
-enum PlayerColor {
-	Red,
-	Black
+// reads the options from the command line arguments, e.g.
+// `--variant italian`, defaulting to American checkers on
+// its regulation board
+fn options_from_args() -> Result<Options, String> {
+	let mut args = env::args().skip(1);
+	let mut variant = Variant::American;
+	let mut board_size = None;
+	let mut starting_rows = None;
+	let mut draw_rules = DrawRules::standard();
+	let mut colors = ColorScheme::red_black();
+	let mut first_color = None;
+	let mut base_time = None;
+	let mut increment = None;
+	let mut delay = None;
+	let mut handicap_color = None;
+	let mut removed = Vec::new();
+	let mut extra_kings = Vec::new();
+	let mut extra_time = None;
+	let mut notation = Notation::Algebraic;
+	let mut fen = None;
+	let mut pdn_file = None;
+	let mut record_file = None;
+	let mut batch_file = None;
+	let mut resume_file = None;
+	let mut autosave_file = Some(DEFAULT_AUTOSAVE_FILE.to_string());
+	
+	while let Some(arg) = args.next() {
+		match &*arg {
+			"--variant" => {
+				let name = try!(args.next()
+					.ok_or("--variant requires a name".to_string()));
+				variant = try!(Variant::from_name(&name)
+					.ok_or(format!("Unknown variant '{}'", name)));
+			},
+			"--size" => board_size = Some(try!(parse_number_arg(&arg, args.next()))),
+			"--rows" => starting_rows = Some(try!(parse_number_arg(&arg, args.next()))),
+			"--repetitions" => {
+				let repetitions = try!(parse_number_arg(&arg, args.next()));
+				draw_rules.repetitions = if repetitions == 0 { None } else { Some(repetitions) };
+			},
+			"--move-limit" => {
+				let move_limit = try!(parse_number_arg(&arg, args.next()));
+				draw_rules.move_limit = if move_limit == 0 { None } else { Some(move_limit) };
+			},
+			"--colors" => {
+				let names = try!(args.next()
+					.ok_or("--colors requires two names, e.g. red,black".to_string()));
+				colors = try!(parse_colors(&names));
+			},
+			"--first" => first_color = Some(try!(args.next()
+				.ok_or("--first requires the name of a color".to_string()))),
+			"--time" => base_time = Some(try!(parse_number_arg(&arg, args.next()))),
+			"--increment" => increment = Some(try!(parse_number_arg(&arg, args.next()))),
+			"--delay" => delay = Some(try!(parse_number_arg(&arg, args.next()))),
+			"--handicap" => handicap_color = Some(try!(args.next()
+				.ok_or("--handicap requires the name of a color".to_string()))),
+			"--remove" => removed.extend(try!(parse_positions_arg(&arg, args.next()))),
+			"--extra-king" => extra_kings.extend(try!(parse_positions_arg(&arg, args.next()))),
+			"--extra-time" => extra_time = Some(try!(parse_number_arg(&arg, args.next()))),
+			"--notation" => {
+				let name = try!(args.next()
+					.ok_or("--notation requires algebraic or numeric".to_string()));
+				notation = match &*name.to_lowercase() {
+					"algebraic" => Notation::Algebraic,
+					"numeric" => Notation::Numeric,
+					_ => return Err(format!("Unknown notation '{}'", name))
+				};
+			},
+			"--fen" => fen = Some(try!(args.next()
+				.ok_or("--fen requires a position, e.g. W:W21,22,K30:B1,2,K5".to_string()))),
+			"--pdn" => pdn_file = Some(try!(args.next()
+				.ok_or("--pdn requires the name of a file".to_string()))),
+			"--record" => record_file = Some(try!(args.next()
+				.ok_or("--record requires the name of a file".to_string()))),
+			"--batch" => batch_file = Some(try!(args.next()
+				.ok_or("--batch requires the name of a file, or - for standard input".to_string()))),
+			"--resume" => resume_file = Some(try!(args.next()
+				.ok_or("--resume requires the name of a file".to_string()))),
+			"--autosave" => autosave_file = Some(try!(args.next()
+				.ok_or("--autosave requires the name of a file".to_string()))),
+			"--no-autosave" => autosave_file = None,
+			_ => return Err(format!("Unknown argument '{}'", arg))
+		}
+	}
+	
+	// a loaded game brings its own variant and position, and
+	// its moves are replayed with the standard draw rules
+	let sets_up_position = variant != Variant::American || board_size.is_some()
+		|| starting_rows.is_some() || first_color.is_some() || handicap_color.is_some();
+	let sets_draw_rules = draw_rules != DrawRules::standard();
+	if pdn_file.is_some() && (sets_up_position || sets_draw_rules) {
+		return Err(concat!(
+			"--pdn cannot be used with --variant, --size, --rows, --first, --handicap, ",
+			"--repetitions or --move-limit").to_string());
+	}
+	
+	// a saved game brings its own setup, draw rules included
+	if resume_file.is_some() && (sets_up_position || sets_draw_rules || pdn_file.is_some()
+			|| fen.is_some()) {
+		return Err(concat!(
+			"--resume cannot be used with --variant, --size, --rows, --first, --handicap, ",
+			"--repetitions, --move-limit, --pdn or --fen").to_string());
+	}
+	
+	// the position gives the side to move, and is
+	// set up on the regulation board
+	if fen.is_some() && (pdn_file.is_some() || board_size.is_some()
+			|| starting_rows.is_some() || first_color.is_some()) {
+		return Err("--fen cannot be used with --pdn, --size, --rows or --first".to_string());
+	}
+	
+	// a script is played without a clock, and
+	// its output is the only thing printed
+	if batch_file.is_some() && (base_time.is_some() || record_file.is_some()) {
+		return Err("--batch cannot be used with --time or --record".to_string());
+	}
+	
+	// a script is replayed rather than resumed
+	if batch_file.is_some() {
+		autosave_file = None;
+	}
+	
+	let board_size = board_size.unwrap_or(variant.board_size());
+	let starting_rows = starting_rows.unwrap_or(variant.starting_rows());
+	
+	if board_size == 0 || board_size % 2 != 0 || board_size > checkers::MAX_BOARD_SIZE {
+		return Err(format!("The board size must be a positive even number up to {}, found {}",
+			checkers::MAX_BOARD_SIZE, board_size));
+	}
+	
+	// leave at least two empty rows between the players
+	if starting_rows == 0 || 2 * starting_rows + 2 > board_size {
+		return Err(format!(
+			"{} starting rows do not fit on a board of size {}", starting_rows, board_size));
+	}
+	
+	let first_player_id = match first_color {
+		Some(name) => try!(colors.player_id(&name)
+			.ok_or(format!("'{}' is not one of the colors", name))),
+		None => 1
+	};
+	
+	let bonus = match (increment, delay) {
+		(None, None) => TimeBonus::None,
+		(Some(seconds), None) => TimeBonus::Increment(Duration::from_secs(seconds as u64)),
+		(None, Some(seconds)) => TimeBonus::Delay(Duration::from_secs(seconds as u64)),
+		(Some(_), Some(_)) =>
+			return Err("--increment and --delay cannot be used together".to_string())
+	};
+	
+	let time_control = match base_time {
+		Some(minutes) if minutes > 0 => Some(TimeControl{
+			base : Duration::from_secs(60 * minutes as u64), bonus : bonus}),
+		Some(_) => return Err("--time requires at least one minute".to_string()),
+		None if bonus != TimeBonus::None =>
+			return Err("--increment and --delay require --time".to_string()),
+		None => None
+	};
+	
+	if extra_time.is_some() && time_control.is_none() {
+		return Err("--extra-time requires --time".to_string());
+	}
+	
+	// the stronger color gives the odds
+	let handicap = match handicap_color {
+		Some(name) => {
+			let player_id = try!(colors.player_id(&name)
+				.ok_or(format!("'{}' is not one of the colors", name)));
+			let handicap = removed.into_iter().fold(Handicap::new(player_id), Handicap::remove);
+			let handicap = extra_kings.into_iter().fold(handicap, Handicap::extra_king);
+			Some(handicap.extra_time(Duration::from_secs(60 * extra_time.unwrap_or(0) as u64)))
+		},
+		None if !removed.is_empty() || !extra_kings.is_empty() || extra_time.is_some() =>
+			return Err("--remove, --extra-king and --extra-time require --handicap".to_string()),
+		None => None
+	};
+	
+	Ok(Options{
+		variant : variant,
+		board_size : board_size,
+		starting_rows : starting_rows,
+		draw_rules : draw_rules,
+		colors : colors,
+		first_player_id : first_player_id,
+		time_control : time_control,
+		handicap : handicap,
+		notation : notation,
+		fen : fen,
+		pdn_file : pdn_file,
+		record_file : record_file,
+		batch_file : batch_file,
+		resume_file : resume_file,
+		autosave_file : autosave_file})
 }
 
-fn player_id_to_color(player_id : u32) -> PlayerColor {
-	match player_id {
-		1 => PlayerColor::Red,
-		2 => PlayerColor::Black,
-		_ => unreachable!()
+// plays the script in the file at `path`, or on standard input
+// if `path` is `-`, and returns the exit status of batch mode
+fn run_batch(game : &mut Game, path : &str, options : &Options) -> i32 {
+	let mut writer = stdout();
+	let result = if path == "-" {
+		let input = stdin();
+		let script = input.lock();
+		batch::run_script(game, script, &mut writer, &options.colors, options.notation)
+	} else {
+		File::open(path).and_then(|file| batch::run_script(
+			game, BufReader::new(file), &mut writer, &options.colors, options.notation))
+	};
+	
+	match result {
+		Ok(status) => status,
+		Err(error) => {
+			println!("error 0 unreadable {}", error);
+			batch::EXIT_SCRIPT_ERROR
+		}
 	}
 }
 
-fn player_color_to_name(color : PlayerColor) -> &'static str {
-	match color {
-		PlayerColor::Red => "Red",
-		PlayerColor::Black => "Black"
+// sets up the game selected by the options, from the
+// PDN file or the FEN position if one is given
+fn new_game(options : &Options) -> Result<Game, String> {
+	let colors = &options.colors;
+	let mut game = match (&options.pdn_file, &options.fen) {
+		(&Some(ref path), _) => return load_pdn_game(path),
+		(_, &Some(ref fen)) => try!(checkers::read_fen(options.variant, fen)
+			.map_err(|error| fen_error_message(error, colors))),
+		_ => {
+			let mut game = Game::with_board_size(
+				options.variant, options.board_size, options.starting_rows);
+			game.set_first_player(options.first_player_id);
+			game
+		}
+	};
+	
+	game.set_draw_rules(options.draw_rules);
+	
+	if let Some(ref handicap) = options.handicap {
+		try!(game.set_handicap(handicap.clone())
+			.map_err(|error| setup_error_message(error, colors)));
 	}
+	
+	Ok(game)
+}
+
+fn new_clock(options : &Options, game : &Game) -> Option<Clock> {
+	options.time_control.map(|time_control| match game.handicap() {
+		Some(handicap) => handicap.new_clock(time_control),
+		None => Clock::new(time_control)
+	})
 }
 
 fn main() {
-    println!("Welcome to Draughts!");
+	let options = match options_from_args() {
+		Ok(options) => options,
+		Err(message) => {
+			println!("{}", message);
+			process::exit(1);
+		}
+	};
+	
+	let colors = options.colors.clone();
+	let game_result = match options.resume_file {
+		Some(ref path) => load_saved_game(path, &colors),
+		None => new_game(&options)
+	};
+	let mut game = match game_result {
+		Ok(game) => game,
+		Err(message) => {
+			println!("{}", message);
+			process::exit(1);
+		}
+	};
+	
+	if let Some(ref path) = options.batch_file {
+		process::exit(run_batch(&mut game, path, &options));
+	}
+	
+    println!("Welcome to {} Draughts!", game.variant().name());
+	report_moves(&mut game, &colors, options.notation);
 
 	let mut writer = stdout();
-	let mut game = Game::new();
+	
+	if let Some(handicap) = game.handicap() {
+		print_handicap(handicap, &colors);
+	}
+	
+	let mut clock = new_clock(&options, &game);
 	let mut line = String::new();
 	
-	checkers::print_board(&mut writer, game.board()).unwrap();
+	// the player who sees the board from their side
+	let mut facing = 1;
+	
+	let mut autosave_file = options.autosave_file.clone();
+	
+	checkers::print_board_facing(&mut writer, game.board(), &colors, facing).unwrap();
+	print_summary(&game, &colors);
 	
 	'game_loop: loop {
 		line.clear();
 		
-		let player_name = player_color_to_name(
-			player_id_to_color(
-				game.current_player().id));
+		// a game loaded from a file may already be over
+		if *game.state() != GameState::InProgress {
+			print_game_result(&game, &colors);
+			break 'game_loop;
+		}
 		
-		print!("\n{}'s move: ", player_name);
+		let player_id = game.current_player().id;
+		let player_name = colors.name(player_id);
 		
-		for _ in stdout().flush().err() {
-			panic!("Something really bad happened!");
+		// hand the clock over once a move is made
+		if let Some(ref mut clock) = clock {
+			if clock.running_player() != Some(player_id) {
+				let now = Instant::now();
+				clock.end_turn(now);
+				clock.start_turn(player_id, now);
+			}
 		}
 		
-		for _ in stdin().read_line(&mut line).err() {
-			panic!("Something really bad happened!");
+		match game.draw_offer() {
+			Some(offering_id) if offering_id != player_id => println!(
+				"\n{} offers a draw. Enter 'accept' or 'decline', or make a move.",
+				colors.name(offering_id)),
+			_ => { }
 		}
 		
-		let line = line.trim();
+		let time_left = match clock {
+			Some(ref clock) => format!(" ({})",
+				checkers::format_clock_time(clock.remaining(player_id, Instant::now()))),
+			None => String::new()
+		};
 		
-		match line {
-			"Q" | "q" => {
-				println!("\nGiving up so soon?");
-				break 'game_loop;
+		print!("\n{}'s move{}: ", player_name, time_left);
+		
+		if stdout().flush().is_err() {
+			process::exit(1);
+		}
+		
+		match stdin().read_line(&mut line) {
+			// the input ended, e.g. at the end of a pipe, which
+			// leaves the game unfinished
+			Ok(0) => {
+				println!("");
+				return;
+			},
+			Ok(_) => { },
+			Err(error) => {
+				println!("\n *** Cannot read the input: {}", error);
+				process::exit(1);
 			}
-			_ => { }
 		}
 		
-		let parse_result = checkers::parse_move(&line);
+		let line = line.trim();
 		
-		match parse_result {
-			Ok(positions) => {
-				let move_result = apply_positions_as_move(&mut game, positions);
-				match move_result {
-					Ok(game_state) => match game_state {
-						GameState::InProgress => { },
-						GameState::GameOver{winner_id} => {
-							let player_name = player_color_to_name(
-								player_id_to_color(winner_id));
-							
-							println!("\nGame over! {} won!", player_name);
-							break 'game_loop;
-						}
+		let out_of_time = clock.as_ref()
+			.map(|clock| clock.is_flagged(player_id, Instant::now()))
+			.unwrap_or(false);
+		if out_of_time {
+			game.lose_on_time(player_id).unwrap();
+			print_game_result(&game, &colors);
+			break 'game_loop;
+		}
+		
+		let command = match checkers::parse_command(line) {
+			Some(Ok(command)) => Some(command),
+			Some(Err(error)) => {
+				print_command_error(error);
+				continue 'game_loop;
+			},
+			None => None
+		};
+		
+		if let Some(ref command) = command {
+			if let Some(action_result) = apply_action(&mut game, &colors, player_id, command) {
+				match action_result {
+					Ok(GameState::InProgress) => { },
+					Ok(_) => {
+						print_game_result(&game, &colors);
+						break 'game_loop;
 					},
-					Err(e) => match e {
-						MoveError::InvalidMove => println!("\n *** Illegal move"),
-						MoveError::ShouldHaveJumped => println!("\n *** Must take jump")
-					}
+					Err(e) => print_action_error(e)
 				}
+				continue 'game_loop;
+			}
+		}
+		
+		match command {
+			Some(Command::Help) => {
+				print_help();
+				continue 'game_loop;
+			},
+			Some(Command::Moves) => {
+				print_legal_moves(&game, options.notation);
+				continue 'game_loop;
+			},
+			Some(Command::Board) => { },
+			Some(Command::Undo) => if !game.undo() {
+				println!("\n *** There is no move to undo");
+			},
+			Some(Command::Redo) => if !game.redo() {
+				println!("\n *** There is no move to redo");
+			},
+			Some(Command::Save{path}) => match save_game(&game, &path) {
+				Ok(()) => println!("\nThe game was saved in '{}'", path),
+				Err(message) => println!("\n *** {}", message)
+			},
+			Some(Command::Load{path}) => match load_saved_game(&path, &colors) {
+				Ok(loaded) => {
+					game = loaded;
+					report_moves(&mut game, &colors, options.notation);
+					clock = new_clock(&options, &game);
+					println!("\nThe game was loaded from '{}'", path);
+				},
+				Err(message) => println!("\n *** {}", message)
+			},
+			Some(Command::Flip) => facing = if facing == 1 { 2 } else { 1 },
+			Some(Command::Fen) => println!("\n{}", checkers::write_fen(&game)),
+			Some(Command::New) => match new_game(&options) {
+				Ok(new) => {
+					game = new;
+					report_moves(&mut game, &colors, options.notation);
+					clock = new_clock(&options, &game);
+					println!("\nA new game has started");
+				},
+				Err(message) => println!("\n *** {}", message)
 			},
-			Err(e) => match e {
-				InputError::TooFewTokens =>
-					println!("\n *** You must specify at least two board positions"),
-				InputError::InvalidTokens{tokens : errors} => {
-					for error in errors {
-						match error {
-							TokenError::MissingFile{token} =>
-								println!("\n *** Board position '{}' must specify file", token),
-							TokenError::MissingRank{token} =>
-								println!("\n *** Board position '{}' must specify rank", token),
-							TokenError::ZeroRank{token} =>
-								println!("\n *** Rank cannot be zero: {}", token),
-							TokenError::InvalidCharacter{token, char_index} => {
-								let ch = token.chars().nth(char_index).unwrap();
-								println!("\n *** Board position '{}' contains invalid character '{}'", token, ch);
+			Some(_) => unreachable!(),
+			None => {
+				let size = game.board().number_rows();
+				let parse_result = checkers::resolve_move(&line, &game)
+					.and_then(|input| {
+						// moves that cannot be found are reported when they are made
+						if let Ok(the_move) = game.find_move(&input.positions) {
+							try!(input.check_capture_marker(&the_move));
+						}
+						Ok(input.positions)
+					});
+				
+				match parse_result {
+					Ok(positions) => {
+						match apply_positions_as_move(&mut game, positions) {
+							Ok(GameState::InProgress) => autosave(&game, &mut autosave_file),
+							Ok(_) => {
+								print_game_result(&game, &colors);
+								break 'game_loop;
+							},
+							Err(e) => match e {
+								MoveError::IncompletePath =>
+									println!("\n *** You must specify at least two board positions"),
+								MoveError::OffBoard{position} =>
+									println!("\n *** {} is not on the board",
+										checkers::format_position(position)),
+								MoveError::LightTile{position} =>
+									println!("\n *** {} is a light tile, pieces only move on dark tiles",
+										checkers::format_position(position)),
+								MoveError::EmptyStart{position} =>
+									println!("\n *** There is no piece on {}",
+										checkers::format_position(position)),
+								MoveError::OpponentPiece{position} => {
+									let opponent_id = if game.current_player().id == 1 { 2 } else { 1 };
+									println!("\n *** The piece on {} belongs to {}",
+										checkers::format_position(position), colors.name(opponent_id));
+								},
+								MoveError::InvalidDirection{position} =>
+									println!("\n *** The piece cannot move in a straight line to {}",
+										checkers::format_position(position)),
+								MoveError::InvalidMove{position} =>
+									println!("\n *** Illegal move at {}",
+										checkers::format_position(position)),
+								MoveError::ShouldHaveJumped => println!("\n *** Must take jump"),
+								MoveError::GameFinished => unreachable!()
+							}
+						}
+					},
+					Err(e) => match e {
+						InputError::TooFewTokens =>
+							println!("\n *** You must specify at least two board positions"),
+						InputError::MixedMarkers =>
+							println!("\n *** A move cannot be marked with both '-' and 'x'"),
+						InputError::NotACapture =>
+							println!("\n *** This move captures nothing, use '-' instead of 'x'"),
+						InputError::UnmarkedCapture =>
+							println!("\n *** This move is a capture, use 'x' instead of '-'"),
+						InputError::NoMatchingMove{position} =>
+							println!("\n *** No move ends at or jumps from {}",
+								checkers::format_position(position)),
+						InputError::AmbiguousMove{candidates} => {
+							println!("\n *** '{}' matches more than one move:", line.trim());
+							for candidate in candidates {
+								println!("       {}", checkers::format_move(
+									&candidate, options.notation, game.variant(), size));
+							}
+						},
+						InputError::InvalidTokens{tokens : errors} => {
+							for error in errors {
+								match error {
+									TokenError::MissingFile{token} =>
+										println!("\n *** Board position '{}' must specify file", token),
+									TokenError::MissingRank{token} =>
+										println!("\n *** Board position '{}' must specify rank", token),
+									TokenError::ZeroRank{token} =>
+										println!("\n *** Rank cannot be zero: {}", token),
+									TokenError::SquareOutOfRange{token, max_square} =>
+										println!("\n *** Square '{}' must be between 1 and {}", token, max_square),
+									TokenError::PositionTooLarge{token} =>
+										println!("\n *** Board position '{}' is far off the board", token),
+									TokenError::InvalidCharacter{token, char_index} => {
+										let ch = token.chars().nth(char_index).unwrap();
+										println!("\n *** Board position '{}' contains invalid character '{}'", token, ch);
+									}
+								}
 							}
 						}
 					}
@@ -137,6 +981,18 @@ fn main() {
 		}
 		
 		println!("");
-		checkers::print_board(&mut writer, game.board()).unwrap();
+		checkers::print_board_facing(&mut writer, game.board(), &colors, facing).unwrap();
+		print_summary(&game, &colors);
+	}
+	
+	println!("");
+	checkers::print_board_facing(&mut writer, game.board(), &colors, facing).unwrap();
+	print_report(&game, &colors);
+	
+	if let Some(ref path) = options.record_file {
+		match record_game(&game, path) {
+			Ok(()) => println!("\nThe game was recorded in '{}'", path),
+			Err(message) => println!("\n{}", message)
+		}
 	}
 }